@@ -2,10 +2,37 @@ use crate::core::error::{ConfigError, ConfigResult};
 use crate::core::types::{DatabaseConfig, DatabaseType, PoolConfig, SslConfig, TimeoutConfig, SslMode};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::Path;
 use std::time::Duration;
 use url::Url;
 
+/// 分层配置文件的格式，按文件扩展名判断
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ConfigFileFormat {
+    Toml,
+    Yaml,
+}
+
+impl ConfigFileFormat {
+    fn from_path(path: &Path) -> ConfigResult<Self> {
+        match path.extension().and_then(|e| e.to_str()) {
+            Some("toml") => Ok(Self::Toml),
+            Some("yaml") | Some("yml") => Ok(Self::Yaml),
+            other => Err(ConfigError::ParseError(format!(
+                "无法从扩展名推断配置文件格式: {:?}（仅支持.toml/.yaml/.yml）",
+                other
+            ))),
+        }
+    }
+}
+
 /// 配置管理器
+///
+/// 只依赖`serde`/`url`/标准库，不牵涉任何具体驱动，因此能编译到
+/// `wasm32-unknown-unknown`：解析`DatabaseType`、从URL/字典/环境变量构建
+/// `DatabaseConfig`、校验字段，都能在edge/浏览器侧的"driver adapter"部署中
+/// 独立使用——`databases`/`utils`/`python`整个都依赖原生驱动或pyo3，不随
+/// wasm32目标编译，实际建连由宿主环境提供的传输层完成，这个crate本身不参与
 pub struct ConfigManager {
     configs: HashMap<String, DatabaseConfig>,
 }
@@ -71,6 +98,14 @@ impl ConfigManager {
             ssl_config,
             timeout_config,
             application_name: query_params.get("application_name").cloned(),
+            allow_load_extension: query_params
+                .get("allow_load_extension")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            prepared_cache_size: query_params
+                .get("prepared_cache_size")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(256),
         })
     }
 
@@ -125,24 +160,196 @@ impl ConfigManager {
             ssl_config: None,
             timeout_config: TimeoutConfig::default(),
             application_name: config_dict.get("application_name").cloned(),
+            allow_load_extension: config_dict
+                .get("allow_load_extension")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            prepared_cache_size: config_dict
+                .get("prepared_cache_size")
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(256),
         })
     }
 
     /// 从环境变量创建配置
+    ///
+    /// 不含`__`的键按原有方式进入`from_dict`（只覆盖顶层的字符串字段）；
+    /// 形如`key__subkey`的键会被`__`拆成多段路径，展开为嵌套JSON对象后合并进
+    /// 由`from_dict`产出的默认配置，用于覆盖`pool_config`/`ssl_config`/
+    /// `timeout_config`等嵌套字段，例如：
+    /// `DB_POOL_CONFIG__MAX_CONNECTIONS=100`、
+    /// `DB_TIMEOUT_CONFIG__QUERY_TIMEOUT__SECS=10`
     pub fn from_env(prefix: &str) -> ConfigResult<DatabaseConfig> {
         let mut config_dict = HashMap::new();
+        let mut nested_overrides = serde_json::Value::Object(serde_json::Map::new());
+        let mut has_nested = false;
 
-        // 读取环境变量
         for (key, value) in std::env::vars() {
-            if key.starts_with(prefix) {
-                let config_key = key.strip_prefix(prefix)
-                    .unwrap()
-                    .to_lowercase();
+            let Some(suffix) = key.strip_prefix(prefix) else {
+                continue;
+            };
+            let config_key = suffix.to_lowercase();
+
+            if config_key.contains("__") {
+                has_nested = true;
+                let path: Vec<&str> = config_key.split("__").collect();
+                Self::set_nested_json(&mut nested_overrides, &path, &value);
+            } else {
                 config_dict.insert(config_key, value);
             }
         }
 
-        Self::from_dict(config_dict)
+        let base = Self::from_dict(config_dict)?;
+
+        if !has_nested {
+            return Ok(base);
+        }
+
+        let mut merged = serde_json::to_value(&base)
+            .map_err(|e| ConfigError::ParseError(format!("序列化默认配置失败: {}", e)))?;
+        Self::deep_merge_json(&mut merged, nested_overrides);
+
+        serde_json::from_value(merged)
+            .map_err(|e| ConfigError::ParseError(format!("嵌套环境变量字段不匹配: {}", e)))
+    }
+
+    /// 按`path`逐层展开`root`（必须是`serde_json::Value::Object`）并在叶子
+    /// 节点写入`value`（尽量还原成JSON标量，见`coerce_env_value`）
+    fn set_nested_json(root: &mut serde_json::Value, path: &[&str], value: &str) {
+        let Some((head, rest)) = path.split_first() else {
+            return;
+        };
+        let obj = root
+            .as_object_mut()
+            .expect("root固定以Value::Object初始化，调用方不会替换其类型");
+
+        if rest.is_empty() {
+            obj.insert((*head).to_string(), Self::coerce_env_value(value));
+            return;
+        }
+
+        let entry = obj
+            .entry((*head).to_string())
+            .or_insert_with(|| serde_json::Value::Object(serde_json::Map::new()));
+        Self::set_nested_json(entry, rest, value);
+    }
+
+    /// 把环境变量的字符串值尽量还原成JSON标量：整数/浮点数/布尔优先，
+    /// 都不匹配时保留为字符串
+    fn coerce_env_value(value: &str) -> serde_json::Value {
+        if let Ok(i) = value.parse::<i64>() {
+            serde_json::Value::from(i)
+        } else if let Ok(f) = value.parse::<f64>() {
+            serde_json::Value::from(f)
+        } else if let Ok(b) = value.parse::<bool>() {
+            serde_json::Value::from(b)
+        } else {
+            serde_json::Value::from(value)
+        }
+    }
+
+    /// 把`overrides`递归合并进`base`：两边都是对象时逐key合并，否则`overrides`
+    /// 直接覆盖`base`
+    fn deep_merge_json(base: &mut serde_json::Value, overrides: serde_json::Value) {
+        match (base, overrides) {
+            (serde_json::Value::Object(base_map), serde_json::Value::Object(override_map)) => {
+                for (key, value) in override_map {
+                    match base_map.get_mut(&key) {
+                        Some(existing) => Self::deep_merge_json(existing, value),
+                        None => {
+                            base_map.insert(key, value);
+                        }
+                    }
+                }
+            }
+            (base_slot, value) => {
+                *base_slot = value;
+            }
+        }
+    }
+
+    /// 从分层的TOML/YAML配置文件加载某个profile的配置
+    ///
+    /// 文件顶层是`default`表与`profiles.<name>`表；加载时先取`default`作为
+    /// 基础，再用`profiles.<profile>`里的同名顶层键覆盖它（一层浅合并，不递归
+    /// 进`pool_config`等嵌套表内部——要自定义`pool_config`需要在profile里整体
+    /// 重写这张表），合并结果再按`DatabaseConfig`的字段结构反序列化
+    ///
+    /// 格式按文件扩展名判断：`.toml`按TOML解析，`.yaml`/`.yml`按YAML解析
+    ///
+    /// ```toml
+    /// [default]
+    /// db_type = "postgresql"
+    /// host = "localhost"
+    /// port = 5432
+    /// database = "app"
+    /// username = "app"
+    /// password = "secret"
+    ///
+    /// [profiles.production]
+    /// host = "prod-db.internal"
+    /// ```
+    pub fn from_layered_file(path: impl AsRef<Path>, profile: &str) -> ConfigResult<DatabaseConfig> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path).map_err(|e| {
+            ConfigError::ParseError(format!("读取配置文件 {} 失败: {}", path.display(), e))
+        })?;
+
+        match ConfigFileFormat::from_path(path)? {
+            ConfigFileFormat::Toml => Self::from_layered_toml(&content, profile),
+            ConfigFileFormat::Yaml => Self::from_layered_yaml(&content, profile),
+        }
+    }
+
+    fn from_layered_toml(content: &str, profile: &str) -> ConfigResult<DatabaseConfig> {
+        let root: toml::Value = toml::from_str(content)
+            .map_err(|e| ConfigError::ParseError(format!("解析TOML失败: {}", e)))?;
+
+        let mut merged = root
+            .get("default")
+            .cloned()
+            .unwrap_or_else(|| toml::Value::Table(toml::value::Table::new()));
+
+        let overrides = root
+            .get("profiles")
+            .and_then(|p| p.get(profile))
+            .ok_or_else(|| ConfigError::MissingRequired(format!("未找到profile: {}", profile)))?;
+
+        let (Some(base_table), Some(override_table)) = (merged.as_table_mut(), overrides.as_table()) else {
+            return Err(ConfigError::ParseError("default/profiles必须是表".to_string()));
+        };
+        for (key, value) in override_table {
+            base_table.insert(key.clone(), value.clone());
+        }
+
+        merged
+            .try_into()
+            .map_err(|e: toml::de::Error| ConfigError::ParseError(format!("配置字段不匹配: {}", e)))
+    }
+
+    fn from_layered_yaml(content: &str, profile: &str) -> ConfigResult<DatabaseConfig> {
+        let root: serde_yaml::Value = serde_yaml::from_str(content)
+            .map_err(|e| ConfigError::ParseError(format!("解析YAML失败: {}", e)))?;
+
+        let mut merged = root
+            .get("default")
+            .cloned()
+            .unwrap_or_else(|| serde_yaml::Value::Mapping(serde_yaml::Mapping::new()));
+
+        let overrides = root
+            .get("profiles")
+            .and_then(|p| p.get(profile))
+            .ok_or_else(|| ConfigError::MissingRequired(format!("未找到profile: {}", profile)))?;
+
+        let (Some(base_mapping), Some(override_mapping)) = (merged.as_mapping_mut(), overrides.as_mapping()) else {
+            return Err(ConfigError::ParseError("default/profiles必须是映射".to_string()));
+        };
+        for (key, value) in override_mapping {
+            base_mapping.insert(key.clone(), value.clone());
+        }
+
+        serde_yaml::from_value(merged)
+            .map_err(|e| ConfigError::ParseError(format!("配置字段不匹配: {}", e)))
     }
 
     /// 验证配置
@@ -167,6 +374,24 @@ impl ConfigManager {
             return Err(ConfigError::ValidationFailed("最大连接数必须大于0".to_string()));
         }
 
+        if let Some(ssl_config) = &config.ssl_config {
+            let needs_ca = matches!(ssl_config.ssl_mode, SslMode::VerifyCa | SslMode::VerifyFull);
+
+            // trust_server_certificate意味着放弃证书校验，与要求严格校验的
+            // VerifyCa/VerifyFull自相矛盾，拒绝而不是静默选择其中一个
+            if needs_ca && ssl_config.trust_server_certificate {
+                return Err(ConfigError::ValidationFailed(
+                    "SslMode::VerifyCa/VerifyFull与trust_server_certificate=true相互矛盾，请二选一".to_string(),
+                ));
+            }
+
+            if needs_ca && ssl_config.certificate_path.is_none() {
+                return Err(ConfigError::MissingRequired(
+                    "SslMode::VerifyCa/VerifyFull需要提供certificate_path指向的CA证书".to_string(),
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -199,6 +424,8 @@ impl ConfigManager {
                 "disable" => SslMode::Disable,
                 "require" => SslMode::Require,
                 "prefer" => SslMode::Prefer,
+                "verify-ca" | "verify_ca" => SslMode::VerifyCa,
+                "verify-full" | "verify_full" => SslMode::VerifyFull,
                 _ => return Err(ConfigError::InvalidValue(format!("无效的SSL模式: {}", ssl_mode_str))),
             };
 
@@ -263,4 +490,268 @@ impl Default for ConfigManager {
     fn default() -> Self {
         Self::new()
     }
+}
+
+#[cfg(test)]
+mod layered_file_tests {
+    use super::*;
+
+    const TOML_FIXTURE: &str = r#"
+[default]
+db_type = "postgresql"
+host = "localhost"
+port = 5432
+database = "app"
+username = "app"
+password = "secret"
+
+[default.pool_config]
+min_connections = 1
+max_connections = 10
+auto_scaling = false
+scale_up_threshold = 0.8
+scale_down_threshold = 0.3
+
+[default.pool_config.acquire_timeout]
+secs = 5
+nanos = 0
+
+[default.pool_config.idle_timeout]
+secs = 300
+nanos = 0
+
+[default.pool_config.max_lifetime]
+secs = 1800
+nanos = 0
+
+[default.pool_config.health_check_interval]
+secs = 30
+nanos = 0
+
+[default.timeout_config.query_timeout]
+secs = 30
+nanos = 0
+
+[default.timeout_config.connection_timeout]
+secs = 10
+nanos = 0
+
+[default.timeout_config.command_timeout]
+secs = 30
+nanos = 0
+
+[profiles.production]
+host = "prod-db.internal"
+password = "prod-secret"
+"#;
+
+    #[test]
+    fn toml_profile_overrides_default_keys_and_keeps_the_rest() {
+        let config = ConfigManager::from_layered_toml(TOML_FIXTURE, "production").unwrap();
+
+        assert_eq!(config.db_type, DatabaseType::PostgreSQL);
+        assert_eq!(config.host, "prod-db.internal");
+        assert_eq!(config.password, "prod-secret");
+        // default里没被profile覆盖的键原样保留
+        assert_eq!(config.port, 5432);
+        assert_eq!(config.database, "app");
+        assert_eq!(config.username, "app");
+    }
+
+    #[test]
+    fn toml_missing_profile_is_an_error() {
+        let result = ConfigManager::from_layered_toml(TOML_FIXTURE, "staging");
+        assert!(result.is_err());
+    }
+
+    const YAML_FIXTURE: &str = r#"
+default:
+  db_type: postgresql
+  host: localhost
+  port: 5432
+  database: app
+  username: app
+  password: secret
+  pool_config:
+    min_connections: 1
+    max_connections: 10
+    auto_scaling: false
+    scale_up_threshold: 0.8
+    scale_down_threshold: 0.3
+    acquire_timeout:
+      secs: 5
+      nanos: 0
+    idle_timeout:
+      secs: 300
+      nanos: 0
+    max_lifetime:
+      secs: 1800
+      nanos: 0
+    health_check_interval:
+      secs: 30
+      nanos: 0
+  timeout_config:
+    query_timeout:
+      secs: 30
+      nanos: 0
+    connection_timeout:
+      secs: 10
+      nanos: 0
+    command_timeout:
+      secs: 30
+      nanos: 0
+profiles:
+  production:
+    host: prod-db.internal
+    password: prod-secret
+"#;
+
+    #[test]
+    fn yaml_profile_overrides_default_keys_and_keeps_the_rest() {
+        let config = ConfigManager::from_layered_yaml(YAML_FIXTURE, "production").unwrap();
+
+        assert_eq!(config.db_type, DatabaseType::PostgreSQL);
+        assert_eq!(config.host, "prod-db.internal");
+        assert_eq!(config.password, "prod-secret");
+        assert_eq!(config.port, 5432);
+        assert_eq!(config.database, "app");
+        assert_eq!(config.username, "app");
+    }
+
+    #[test]
+    fn yaml_missing_profile_is_an_error() {
+        let result = ConfigManager::from_layered_yaml(YAML_FIXTURE, "staging");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn from_layered_file_picks_parser_by_extension() {
+        let dir = std::env::temp_dir();
+        let path = dir.join(format!(
+            "db_pool_rs_layered_file_test_{}_{}.toml",
+            std::process::id(),
+            "production"
+        ));
+        std::fs::write(&path, TOML_FIXTURE).unwrap();
+
+        let config = ConfigManager::from_layered_file(&path, "production").unwrap();
+        assert_eq!(config.host, "prod-db.internal");
+
+        std::fs::remove_file(&path).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod coerce_env_value_tests {
+    use super::*;
+
+    #[test]
+    fn coerces_integers_floats_and_booleans() {
+        assert_eq!(ConfigManager::coerce_env_value("16"), serde_json::json!(16));
+        assert_eq!(ConfigManager::coerce_env_value("3.14"), serde_json::json!(3.14));
+        assert_eq!(ConfigManager::coerce_env_value("true"), serde_json::json!(true));
+        assert_eq!(ConfigManager::coerce_env_value("false"), serde_json::json!(false));
+    }
+
+    #[test]
+    fn falls_back_to_string_when_nothing_else_matches() {
+        assert_eq!(
+            ConfigManager::coerce_env_value("prod-db.internal"),
+            serde_json::json!("prod-db.internal")
+        );
+    }
+
+    /// 已知边界情况：`"nan"`/`"inf"`能被`str::parse::<f64>`接受，但
+    /// `serde_json::Number::from_f64`对非有限浮点数返回`None`，`Value::from`
+    /// 据此退化为`Value::Null`——字面量字符串`"nan"`因此会变成JSON null而不是
+    /// 字符串"nan"或报错。这里把这个行为钉住，避免未来改动时被悄悄改掉。
+    #[test]
+    fn literal_nan_and_inf_strings_coerce_to_null() {
+        assert_eq!(ConfigManager::coerce_env_value("nan"), serde_json::Value::Null);
+        assert_eq!(ConfigManager::coerce_env_value("inf"), serde_json::Value::Null);
+        assert_eq!(ConfigManager::coerce_env_value("-inf"), serde_json::Value::Null);
+    }
+}
+
+#[cfg(test)]
+mod from_env_tests {
+    use super::*;
+    use serial_test::serial;
+
+    /// 设置一批带`prefix`前缀的环境变量，返回一个清理守卫：守卫被drop时移除
+    /// 这些变量，避免污染同一进程里后续的测试
+    struct EnvVarGuard {
+        keys: Vec<String>,
+    }
+
+    impl EnvVarGuard {
+        fn set(prefix: &str, vars: &[(&str, &str)]) -> Self {
+            let mut keys = Vec::new();
+            for (key, value) in vars {
+                let full_key = format!("{prefix}{key}");
+                std::env::set_var(&full_key, value);
+                keys.push(full_key);
+            }
+            Self { keys }
+        }
+    }
+
+    impl Drop for EnvVarGuard {
+        fn drop(&mut self) {
+            for key in &self.keys {
+                std::env::remove_var(key);
+            }
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn nested_double_underscore_keys_populate_pool_and_ssl_config() {
+        let prefix = "DBPOOLRS_TEST_CHUNK5_2_";
+        let _guard = EnvVarGuard::set(
+            prefix,
+            &[
+                ("DB_TYPE", "postgresql"),
+                ("HOST", "localhost"),
+                ("DATABASE", "app"),
+                ("USERNAME", "app"),
+                ("PASSWORD", "secret"),
+                ("POOL_CONFIG__MAX_CONNECTIONS", "16"),
+                ("POOL_CONFIG__ACQUIRE_TIMEOUT__SECS", "5"),
+                ("SSL_CONFIG__SSL_MODE", "Require"),
+                ("SSL_CONFIG__TRUST_SERVER_CERTIFICATE", "false"),
+            ],
+        );
+
+        let config = ConfigManager::from_env(prefix).unwrap();
+
+        assert_eq!(config.db_type, DatabaseType::PostgreSQL);
+        assert_eq!(config.pool_config.max_connections, 16);
+        assert_eq!(config.pool_config.acquire_timeout, Duration::from_secs(5));
+        // 未被环境变量覆盖的嵌套字段保留PoolConfig::default()里的值
+        assert_eq!(config.pool_config.min_connections, PoolConfig::default().min_connections);
+
+        let ssl_config = config.ssl_config.unwrap();
+        assert_eq!(ssl_config.ssl_mode, SslMode::Require);
+        assert!(!ssl_config.trust_server_certificate);
+    }
+
+    #[test]
+    #[serial]
+    fn without_any_double_underscore_keys_behaves_like_from_dict() {
+        let prefix = "DBPOOLRS_TEST_CHUNK52_NO_NESTING_";
+        let _guard = EnvVarGuard::set(
+            prefix,
+            &[
+                ("DB_TYPE", "sqlite"),
+                ("DATABASE", "app.db"),
+            ],
+        );
+
+        let config = ConfigManager::from_env(prefix).unwrap();
+        assert_eq!(config.db_type, DatabaseType::SQLite);
+        assert_eq!(config.database, "app.db");
+        assert_eq!(config.pool_config.max_connections, PoolConfig::default().max_connections);
+        assert_eq!(config.pool_config.min_connections, PoolConfig::default().min_connections);
+    }
 }
\ No newline at end of file
@@ -15,6 +15,29 @@ pub enum DbPoolError {
     #[error("数据转换错误: {0}")]
     DataConversion(#[from] ConversionError),
 
+    #[cfg(feature = "mssql-native")]
+    #[error("{0}")]
+    SqlServer(#[from] crate::databases::mssql::error::SqlServerError),
+
+    /// 可归类出SQLSTATE的驱动错误，区别于兜底的`Query::ExecutionFailed`字符串；
+    /// `category`供调用方（如重试逻辑、`AlertManager`）判断是否值得重试。
+    ///
+    /// `severity`/`detail`/`constraint`/`table`/`column`对应PostgreSQL错误响应里的
+    /// `S`/`D`/`n`/`t`/`c`字段（`tokio_postgres::error::DbError`已解析好，直接透传），
+    /// MSSQL没有对应字段，转换时留空；拿不到时也一律是`None`，而不是空字符串，
+    /// 避免调用方把"未知"和"驱动明确返回了空值"混为一谈
+    #[error("数据库错误[{sqlstate}]: {message}")]
+    Database {
+        sqlstate: crate::core::sqlstate::SqlState,
+        category: crate::core::sqlstate::ErrorCategory,
+        message: String,
+        severity: Option<String>,
+        detail: Option<String>,
+        constraint: Option<String>,
+        table: Option<String>,
+        column: Option<String>,
+    },
+
     #[error("监控错误: {0}")]
     Monitoring(String),
 
@@ -22,6 +45,54 @@ pub enum DbPoolError {
     Runtime(String),
 }
 
+impl DbPoolError {
+    /// 是否值得按`RetryPolicy`重新发起同一操作
+    ///
+    /// 既覆盖原有的连接失败（`Connection`），也覆盖能归类出
+    /// `ErrorCategory::Transient`的`Database`错误（如序列化失败、死锁），以及
+    /// 映射到同一`ErrorCategory::Transient`的`SqlServer`错误（如死锁牺牲品、
+    /// 锁请求超时），供`PoolManager`的重试逻辑统一判断，而不必分别枚举每种
+    /// 错误类型，也不必让MSSQL只靠`execute_transaction`里硬编码的死锁重试。
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Self::Connection(_) => true,
+            Self::Database { category, .. } => *category == crate::core::sqlstate::ErrorCategory::Transient,
+            #[cfg(feature = "mssql-native")]
+            Self::SqlServer(sql_err) => {
+                sql_err.sqlstate().category() == crate::core::sqlstate::ErrorCategory::Transient
+            }
+            _ => false,
+        }
+    }
+
+    /// 是否是唯一约束冲突（SQLSTATE `23505`，MSSQL错误号2627/2601）
+    ///
+    /// 让调用方（如"用户已存在"这类语义分支）按错误语义判断，不必对
+    /// `message`做脆弱的子串匹配
+    pub fn is_unique_violation(&self) -> bool {
+        self.matches_sqlstate(crate::core::sqlstate::SqlState::UniqueViolation)
+    }
+
+    /// 是否是外键约束冲突（SQLSTATE `23503`，MSSQL错误号547）
+    pub fn is_foreign_key_violation(&self) -> bool {
+        self.matches_sqlstate(crate::core::sqlstate::SqlState::ForeignKeyViolation)
+    }
+
+    /// 是否是非空约束冲突（SQLSTATE `23502`，MSSQL错误号515）
+    pub fn is_not_null_violation(&self) -> bool {
+        self.matches_sqlstate(crate::core::sqlstate::SqlState::NotNullViolation)
+    }
+
+    fn matches_sqlstate(&self, target: crate::core::sqlstate::SqlState) -> bool {
+        match self {
+            Self::Database { sqlstate, .. } => *sqlstate == target,
+            #[cfg(feature = "mssql-native")]
+            Self::SqlServer(sql_err) => sql_err.sqlstate() == target,
+            _ => false,
+        }
+    }
+}
+
 /// 连接相关错误
 #[derive(Error, Debug)]
 pub enum ConnectionError {
@@ -104,4 +175,31 @@ pub type Result<T> = std::result::Result<T, DbPoolError>;
 pub type ConnectionResult<T> = std::result::Result<T, ConnectionError>;
 pub type QueryResult<T> = std::result::Result<T, QueryError>;
 pub type ConfigResult<T> = std::result::Result<T, ConfigError>;
-pub type ConversionResult<T> = std::result::Result<T, ConversionError>;
\ No newline at end of file
+pub type ConversionResult<T> = std::result::Result<T, ConversionError>;
+
+#[cfg(all(test, feature = "mssql-native"))]
+mod mssql_transient_tests {
+    use super::DbPoolError;
+    use crate::databases::mssql::error::{SqlServerError, SqlServerErrorCategory};
+
+    fn sql_server_error(number: u32, category: SqlServerErrorCategory) -> DbPoolError {
+        DbPoolError::SqlServer(SqlServerError {
+            number,
+            severity: 13,
+            state: 1,
+            message: "boom".to_string(),
+            category,
+        })
+    }
+
+    #[test]
+    fn deadlock_and_lock_timeout_are_transient() {
+        assert!(sql_server_error(1205, SqlServerErrorCategory::Deadlock).is_transient());
+        assert!(sql_server_error(1222, SqlServerErrorCategory::Timeout).is_transient());
+    }
+
+    #[test]
+    fn unique_violation_is_not_transient() {
+        assert!(!sql_server_error(2627, SqlServerErrorCategory::UniqueViolation).is_transient());
+    }
+}
\ No newline at end of file
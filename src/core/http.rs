@@ -0,0 +1,60 @@
+use crate::core::error::{DbPoolError, Result};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// 极简HTTP/1.1响应/请求行读写，被`MetricsServer`和`MonitoringServer`共用，
+/// 避免两个独立手写的`/metrics`端点各自维护一份一模一样的样板代码
+///
+/// 不是通用HTTP实现：只处理"读一行请求行、忽略请求头、回一个定长响应"这一种
+/// 场景，调用方自行决定怎么分发路径
+
+/// 绑定到`addr`，失败时统一包装成`DbPoolError::Monitoring`
+pub(crate) async fn bind(addr: &str) -> Result<TcpListener> {
+    TcpListener::bind(addr)
+        .await
+        .map_err(|e| DbPoolError::Monitoring(format!("监听 {} 失败: {}", addr, e)))
+}
+
+/// 实际绑定到的本地地址（当`addr`使用端口`0`时用于获取分配到的端口）
+pub(crate) fn local_addr(listener: &TcpListener) -> Result<std::net::SocketAddr> {
+    listener
+        .local_addr()
+        .map_err(|e| DbPoolError::Monitoring(format!("获取监听地址失败: {}", e)))
+}
+
+/// 读取请求的第一行（请求行），忽略其余请求头
+pub(crate) async fn read_request_line(stream: &mut TcpStream) -> std::io::Result<String> {
+    let mut buf = [0u8; 1024];
+    let n = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..n]);
+    Ok(request.lines().next().unwrap_or("").to_string())
+}
+
+/// 写出一个状态码为200的响应
+pub(crate) async fn write_response(
+    stream: &mut TcpStream,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    write_response_with_status(stream, 200, "OK", content_type, body).await
+}
+
+/// 写出一个带自定义状态码的响应并关闭连接
+pub(crate) async fn write_response_with_status(
+    stream: &mut TcpStream,
+    status: u16,
+    reason: &str,
+    content_type: &str,
+    body: &str,
+) -> std::io::Result<()> {
+    let response = format!(
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        content_type,
+        body.len(),
+        body
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
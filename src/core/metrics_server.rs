@@ -0,0 +1,60 @@
+use crate::core::error::{DbPoolError, Result};
+use crate::core::http;
+use crate::core::pool_manager::DistributedPoolManager;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+
+/// 轻量级 `/metrics` HTTP端点：不引入完整的HTTP框架，仅解析请求行并按需返回
+/// Prometheus文本暴露格式，供标准监控系统抓取
+///
+/// 监听句柄随返回值丢弃而关闭；调用方通常用 `tokio::spawn` 让其与连接池同生命周期
+pub struct MetricsServer {
+    listener: TcpListener,
+    manager: Arc<DistributedPoolManager>,
+}
+
+impl MetricsServer {
+    /// 绑定到 `addr`（如 `"0.0.0.0:9090"`），失败时返回 `DbPoolError::Monitoring`
+    pub async fn bind(addr: &str, manager: Arc<DistributedPoolManager>) -> Result<Self> {
+        let listener = http::bind(addr).await?;
+        Ok(Self { listener, manager })
+    }
+
+    /// 实际绑定到的本地地址（当 `addr` 使用端口 `0` 时用于获取分配到的端口）
+    pub fn local_addr(&self) -> Result<std::net::SocketAddr> {
+        http::local_addr(&self.listener)
+    }
+
+    /// 持续accept循环，每个连接独立处理；单个连接失败只记录日志，不影响端点继续服务
+    pub async fn serve(self) -> Result<()> {
+        loop {
+            let (stream, _) = self
+                .listener
+                .accept()
+                .await
+                .map_err(|e| DbPoolError::Monitoring(format!("accept失败: {}", e)))?;
+
+            let manager = self.manager.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, manager).await {
+                    tracing::warn!("metrics端点处理连接失败: {}", e);
+                }
+            });
+        }
+    }
+}
+
+/// 读取请求行、丢弃请求头，仅对 `GET /metrics` 返回指标正文，其余路径返回404
+async fn handle_connection(
+    mut stream: tokio::net::TcpStream,
+    manager: Arc<DistributedPoolManager>,
+) -> std::io::Result<()> {
+    let request_line = http::read_request_line(&mut stream).await?;
+
+    if request_line.starts_with("GET /metrics") {
+        let body = manager.render_prometheus().await;
+        http::write_response(&mut stream, "text/plain; version=0.0.4", &body).await
+    } else {
+        http::write_response_with_status(&mut stream, 404, "Not Found", "text/plain", "not found").await
+    }
+}
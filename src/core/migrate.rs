@@ -0,0 +1,284 @@
+use crate::core::error::{DbPoolError, Result};
+use crate::core::pool_manager::DistributedPoolManager;
+use crate::core::types::{BatchOperation, DatabaseValue, QueryParams};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 迁移跟踪表名
+const MIGRATIONS_TABLE: &str = "_db_pool_migrations";
+
+/// 一条版本化迁移脚本
+#[derive(Debug, Clone)]
+pub struct Migration {
+    pub version: u64,
+    pub name: String,
+    pub up_sql: String,
+    pub down_sql: Option<String>,
+    pub checksum: String,
+}
+
+/// 迁移执行器：读取有序的up/down脚本，在 `_db_pool_migrations` 表中追踪已应用版本
+pub struct MigrationRunner;
+
+impl MigrationRunner {
+    /// 从目录加载所有 `NNNN_name.up.sql` / `NNNN_name.down.sql` 文件
+    pub fn load_migrations(dir: &Path) -> Result<Vec<Migration>> {
+        let mut up_scripts: HashMap<u64, (String, String)> = HashMap::new();
+        let mut down_scripts: HashMap<u64, String> = HashMap::new();
+
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| DbPoolError::Runtime(format!("无法读取迁移目录: {}", e)))?;
+
+        for entry in entries {
+            let entry = entry.map_err(|e| DbPoolError::Runtime(format!("读取目录项失败: {}", e)))?;
+            let path = entry.path();
+            let file_name = match path.file_name().and_then(|n| n.to_str()) {
+                Some(n) => n.to_string(),
+                None => continue,
+            };
+
+            if let Some((version, name)) = Self::parse_up_file_name(&file_name) {
+                let sql = std::fs::read_to_string(&path)
+                    .map_err(|e| DbPoolError::Runtime(format!("读取迁移文件失败: {}", e)))?;
+                up_scripts.insert(version, (name, sql));
+            } else if let Some(version) = Self::parse_down_file_name(&file_name) {
+                let sql = std::fs::read_to_string(&path)
+                    .map_err(|e| DbPoolError::Runtime(format!("读取迁移文件失败: {}", e)))?;
+                down_scripts.insert(version, sql);
+            }
+        }
+
+        let mut migrations: Vec<Migration> = up_scripts
+            .into_iter()
+            .map(|(version, (name, up_sql))| {
+                let checksum = Self::checksum(&up_sql);
+                Migration {
+                    version,
+                    name,
+                    up_sql,
+                    down_sql: down_scripts.get(&version).cloned(),
+                    checksum,
+                }
+            })
+            .collect();
+
+        migrations.sort_by_key(|m| m.version);
+        Ok(migrations)
+    }
+
+    fn parse_up_file_name(file_name: &str) -> Option<(u64, String)> {
+        let stem = file_name.strip_suffix(".up.sql")?;
+        Self::split_version_name(stem)
+    }
+
+    fn parse_down_file_name(file_name: &str) -> Option<u64> {
+        let stem = file_name.strip_suffix(".down.sql")?;
+        Self::split_version_name(stem).map(|(version, _)| version)
+    }
+
+    fn split_version_name(stem: &str) -> Option<(u64, String)> {
+        let (version_str, name) = stem.split_once('_')?;
+        let version = version_str.parse::<u64>().ok()?;
+        Some((version, name.to_string()))
+    }
+
+    /// 轻量级校验和（FNV-1a 64位），用于检测迁移脚本内容漂移
+    fn checksum(sql: &str) -> String {
+        const FNV_OFFSET: u64 = 0xcbf29ce484222325;
+        const FNV_PRIME: u64 = 0x100000001b3;
+
+        let mut hash = FNV_OFFSET;
+        for byte in sql.as_bytes() {
+            hash ^= *byte as u64;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        format!("{:016x}", hash)
+    }
+
+    fn any_value_to_u64(value: polars::prelude::AnyValue) -> Option<u64> {
+        use polars::prelude::AnyValue;
+        match value {
+            AnyValue::Int32(i) => Some(i as u64),
+            AnyValue::Int64(i) => Some(i as u64),
+            AnyValue::UInt32(i) => Some(i as u64),
+            AnyValue::UInt64(i) => Some(i),
+            _ => None,
+        }
+    }
+
+    /// 记录一条已应用迁移的INSERT语句；占位符统一用`:name`风格，匹配
+    /// Postgres/MSSQL的`bind_named_params`（只识别`:name`，不识别`@name`）
+    fn insert_applied_sql() -> String {
+        format!(
+            "INSERT INTO {} (version, name, checksum, applied_at) VALUES (:version, :name, :checksum, CURRENT_TIMESTAMP)",
+            MIGRATIONS_TABLE
+        )
+    }
+
+    /// 回滚时删除一条已应用迁移记录的DELETE语句，占位符风格同`insert_applied_sql`
+    fn delete_applied_sql() -> String {
+        format!("DELETE FROM {} WHERE version = :version", MIGRATIONS_TABLE)
+    }
+
+    /// 确保迁移跟踪表存在
+    async fn ensure_migrations_table(manager: &DistributedPoolManager, pool_id: &str) -> Result<()> {
+        manager
+            .execute_non_query(
+                pool_id,
+                &format!(
+                    "CREATE TABLE IF NOT EXISTS {} (version BIGINT PRIMARY KEY, name TEXT, checksum TEXT, applied_at TIMESTAMP)",
+                    MIGRATIONS_TABLE
+                ),
+                None,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// 读取已应用的版本及其校验和
+    async fn applied_versions(
+        manager: &DistributedPoolManager,
+        pool_id: &str,
+    ) -> Result<HashMap<u64, String>> {
+        let df = manager
+            .execute_query(
+                pool_id,
+                &format!("SELECT version, checksum FROM {} ORDER BY version", MIGRATIONS_TABLE),
+                None,
+            )
+            .await?;
+
+        let mut applied = HashMap::new();
+        let version_col = df.column("version").ok();
+        let checksum_col = df.column("checksum").ok();
+
+        if let (Some(versions), Some(checksums)) = (version_col, checksum_col) {
+            for i in 0..df.height() {
+                let version = versions
+                    .get(i)
+                    .ok()
+                    .and_then(Self::any_value_to_u64)
+                    .unwrap_or(0);
+                let checksum = checksums
+                    .get(i)
+                    .ok()
+                    .map(|v| v.to_string())
+                    .unwrap_or_default();
+                applied.insert(version, checksum);
+            }
+        }
+
+        Ok(applied)
+    }
+
+    /// 应用所有未执行的迁移，返回本次应用的版本号列表
+    pub async fn migrate(
+        manager: &DistributedPoolManager,
+        pool_id: &str,
+        migrations_dir: &Path,
+    ) -> Result<Vec<u64>> {
+        Self::ensure_migrations_table(manager, pool_id).await?;
+
+        let migrations = Self::load_migrations(migrations_dir)?;
+        let applied = Self::applied_versions(manager, pool_id).await?;
+        let mut newly_applied = Vec::new();
+
+        for migration in migrations {
+            if let Some(existing_checksum) = applied.get(&migration.version) {
+                if existing_checksum != &migration.checksum {
+                    return Err(DbPoolError::Runtime(format!(
+                        "迁移校验和不匹配，检测到漂移: version={}",
+                        migration.version
+                    )));
+                }
+                continue;
+            }
+
+            manager
+                .execute_transaction(pool_id, vec![BatchOperation { sql: migration.up_sql.clone(), params: None }])
+                .await?;
+
+            let mut params: QueryParams = HashMap::new();
+            params.insert("version".to_string(), DatabaseValue::I64(migration.version as i64));
+            params.insert("name".to_string(), DatabaseValue::String(migration.name.clone()));
+            params.insert("checksum".to_string(), DatabaseValue::String(migration.checksum.clone()));
+
+            manager
+                .execute_non_query(pool_id, &Self::insert_applied_sql(), Some(params))
+                .await?;
+
+            newly_applied.push(migration.version);
+        }
+
+        Ok(newly_applied)
+    }
+
+    /// 回滚到目标版本（含），使用down脚本，返回被回滚的版本号列表
+    pub async fn migrate_to(
+        manager: &DistributedPoolManager,
+        pool_id: &str,
+        migrations_dir: &Path,
+        target_version: u64,
+    ) -> Result<Vec<u64>> {
+        Self::ensure_migrations_table(manager, pool_id).await?;
+
+        let migrations = Self::load_migrations(migrations_dir)?;
+        let migrations_by_version: HashMap<u64, Migration> =
+            migrations.into_iter().map(|m| (m.version, m)).collect();
+
+        let mut applied_versions: Vec<u64> = Self::applied_versions(manager, pool_id)
+            .await?
+            .into_keys()
+            .filter(|v| *v > target_version)
+            .collect();
+        applied_versions.sort_unstable_by(|a, b| b.cmp(a));
+
+        let mut reverted = Vec::new();
+
+        for version in applied_versions {
+            let migration = migrations_by_version.get(&version).ok_or_else(|| {
+                DbPoolError::Runtime(format!("找不到版本 {} 对应的迁移脚本", version))
+            })?;
+
+            let down_sql = migration.down_sql.clone().ok_or_else(|| {
+                DbPoolError::Runtime(format!("版本 {} 没有down脚本，无法回滚", version))
+            })?;
+
+            manager
+                .execute_transaction(pool_id, vec![BatchOperation { sql: down_sql, params: None }])
+                .await?;
+
+            let mut params: QueryParams = HashMap::new();
+            params.insert("version".to_string(), DatabaseValue::I64(version as i64));
+
+            manager
+                .execute_non_query(pool_id, &Self::delete_applied_sql(), Some(params))
+                .await?;
+
+            reverted.push(version);
+        }
+
+        Ok(reverted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MigrationRunner;
+
+    /// `bind_named_params`（Postgres/MSSQL）只识别`:name`风格占位符；SQLite的
+    /// `bind_params`额外接受裸`@name`/`$name`但同样接受`:name`。记录SQL必须用
+    /// `:name`才能在三个后端上都正确绑定，而不是只在SQLite上凑巧工作
+    #[test]
+    fn applied_migration_sql_uses_colon_placeholders() {
+        let insert_sql = MigrationRunner::insert_applied_sql();
+        assert!(insert_sql.contains(":version"));
+        assert!(insert_sql.contains(":name"));
+        assert!(insert_sql.contains(":checksum"));
+        assert!(!insert_sql.contains('@'), "占位符不应是Postgres/MSSQL无法识别的@name风格");
+
+        let delete_sql = MigrationRunner::delete_applied_sql();
+        assert!(delete_sql.contains(":version"));
+        assert!(!delete_sql.contains('@'));
+    }
+}
@@ -0,0 +1,31 @@
+pub mod config;
+pub mod error;
+pub mod sqlstate;
+pub mod types;
+
+/// 以下子模块经由tokio/rustls/polars依赖了原生能力，`wasm32-unknown-unknown`下
+/// 编译失败：`pool_manager`/`prepared`/`row`/`migrate`需要`databases::traits`
+/// 的`DatabasePool`（返回`polars::DataFrame`），`tls`直接用`rustls`做证书校验，
+/// `metrics_server`用`tokio::net::TcpListener`监听。上面的`config`/`error`/
+/// `sqlstate`/`types`不在此列，始终编译
+#[cfg(all(not(target_arch = "wasm32"), feature = "metrics_http"))]
+pub mod metrics_server;
+
+/// `MetricsServer`与`utils::monitoring_server::MonitoringServer`共用的极简
+/// HTTP请求行/响应读写helper，同受两者的feature门控
+#[cfg(all(
+    not(target_arch = "wasm32"),
+    any(feature = "metrics_http", feature = "monitoring_server")
+))]
+pub(crate) mod http;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub mod migrate;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod pool_manager;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod prepared;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod row;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod tls;
@@ -1,20 +1,72 @@
 use crate::core::error::{DbPoolError, Result};
+use crate::core::prepared::{PreparedStatementCache, PreparedStatementHandle};
 use crate::core::types::{
-    DatabaseConfig, DatabaseType, PoolStatus, PoolMetrics, QueryParams, 
-    BatchOperation, BatchResult, FailoverStrategy, DeploymentMode
+    CircuitState, DatabaseConfig, DatabaseType, DatabaseValue, LoadBalanceAlgorithm, PoolStatus,
+    PoolMetrics, QueryParams, BatchOperation, BatchResult, FailoverStrategy, DeploymentMode
 };
 use crate::databases::factory::DatabaseFactory;
-use crate::databases::traits::DatabasePool;
+use crate::databases::traits::{DatabasePool, StatementHandle, TransactionSession};
 use dashmap::DashMap;
+use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use uuid::Uuid;
 
+/// 重试策略：仅对 `DbPoolError::is_transient` 判定为瞬时的错误生效
+/// （`DbPoolError::Connection`，以及归类为`ErrorCategory::Transient`的
+/// `DbPoolError::Database`），`DbPoolError::Query`等其它错误会直接向上抛出，
+/// 不受本策略影响
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// 最大尝试次数（含首次），例如3表示最多重试2次
+    pub max_attempts: u32,
+    /// 首次重试前的基准延迟
+    pub base_delay: Duration,
+    /// 每次重试延迟相对上一次的放大倍数
+    pub multiplier: f64,
+    /// 延迟上限，超过该值后不再继续放大
+    pub max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(50),
+            multiplier: 2.0,
+            max_delay: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// 第 `attempt` 次重试（从0计数）的退避延迟：`min(max_delay, base * multiplier^attempt)`
+    fn backoff_delay(&self, attempt: u32) -> Duration {
+        let scaled = self.base_delay.as_secs_f64() * self.multiplier.powi(attempt as i32);
+        Duration::from_secs_f64(scaled).min(self.max_delay)
+    }
+}
+
+/// 基于xorshift64的极简PRNG，仅用于重试延迟的抖动因子（仓库未引入`rand`依赖）
+///
+/// 非密码学安全，但对于"抖动避免惊群"这个用途完全足够。
+fn next_jitter_fraction(state: &AtomicU64) -> f64 {
+    let mut x = state.load(Ordering::Relaxed);
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    state.store(x, Ordering::Relaxed);
+
+    // 取高32位映射到[0.5, 1.0)的全抖动区间
+    let frac = (x >> 32) as f64 / u32::MAX as f64;
+    0.5 + frac * 0.5
+}
+
 /// 分布式连接池管理器
 pub struct DistributedPoolManager {
-    /// 本地连接池实例
-    local_pools: DashMap<String, Arc<dyn DatabasePool>>,
+    /// 本地连接池实例（与 `HealthMonitor` 共享同一份底层map，供其后台探测任务直接访问）
+    local_pools: Arc<DashMap<String, Arc<dyn DatabasePool>>>,
     /// 连接池配置
     pool_configs: DashMap<String, DatabaseConfig>,
     /// 故障转移策略
@@ -25,21 +77,69 @@ pub struct DistributedPoolManager {
     metrics_collector: Arc<MetricsCollector>,
     /// 健康监控器
     health_monitor: Arc<HealthMonitor>,
+    /// 预编译语句缓存（按 "pool_id\0sql" 为键）
+    prepared_statements: Arc<PreparedStatementCache>,
+    /// 读写分离下选择副本的轮询游标
+    replica_round_robin: AtomicUsize,
+    /// 各连接池当前正在执行的查询数，供“最少连接数”副本选择策略使用
+    in_flight: DashMap<String, AtomicU32>,
+    /// 已见过的SQL对应的后端预编译句柄（按 "实际路由到的pool_id\0sql" 为键），
+    /// `execute_query` 复查到同一SQL时直接走 `execute_prepared`
+    statement_cache: DashMap<String, StatementHandle>,
+    /// 瞬时连接错误的重试策略
+    retry_policy: RwLock<RetryPolicy>,
+    /// 重试抖动PRNG的状态，固定非零种子
+    jitter_state: AtomicU64,
 }
 
 impl DistributedPoolManager {
     /// 创建新的分布式连接池管理器
     pub fn new(deployment_mode: DeploymentMode) -> Self {
+        let local_pools = Arc::new(DashMap::new());
         Self {
-            local_pools: DashMap::new(),
+            health_monitor: Arc::new(HealthMonitor::new(local_pools.clone())),
+            local_pools,
             pool_configs: DashMap::new(),
             failover_strategy: Arc::new(RwLock::new(FailoverStrategy::LocalOnly)),
             deployment_mode,
             metrics_collector: Arc::new(MetricsCollector::new()),
-            health_monitor: Arc::new(HealthMonitor::new()),
+            prepared_statements: Arc::new(PreparedStatementCache::new()),
+            replica_round_robin: AtomicUsize::new(0),
+            in_flight: DashMap::new(),
+            statement_cache: DashMap::new(),
+            retry_policy: RwLock::new(RetryPolicy::default()),
+            jitter_state: AtomicU64::new(0x9E3779B97F4A7C15),
         }
     }
 
+    /// 准备一条语句：登记到缓存并返回可重复执行的句柄
+    ///
+    /// 首次调用只是登记SQL文本；参数/结果列类型OID会在首次 `execute_prepared`
+    /// 之后从实际执行结果回填，后续调用复用同一缓存项，跳过重新解析。
+    pub async fn prepare(&self, pool_id: &str, sql: &str) -> Result<PreparedStatementHandle> {
+        // 确保连接池存在
+        self.get_pool_with_fallback(pool_id).await?;
+
+        let handle = PreparedStatementHandle::new(pool_id.to_string(), sql.to_string());
+        self.prepared_statements.get_or_insert(&handle.cache_key, sql);
+        Ok(handle)
+    }
+
+    /// 执行一条已准备的语句，并用实际结果回填缓存的类型元数据
+    pub async fn execute_prepared(
+        &self,
+        handle: &PreparedStatementHandle,
+        params: Option<QueryParams>,
+    ) -> Result<polars::frame::DataFrame> {
+        if let Some(params) = &params {
+            self.prepared_statements.update_param_types(&handle.cache_key, params);
+        }
+
+        let df = self.execute_query(&handle.pool_id, &handle.sql, params).await?;
+        self.prepared_statements.update_from_dataframe(&handle.cache_key, &df);
+        Ok(df)
+    }
+
     /// 创建连接池
     pub async fn create_pool(&self, pool_id: String, config: DatabaseConfig) -> Result<()> {
         // 验证配置
@@ -51,6 +151,7 @@ impl DistributedPoolManager {
         // 存储配置和连接池
         self.pool_configs.insert(pool_id.clone(), config);
         self.local_pools.insert(pool_id.clone(), pool);
+        self.in_flight.insert(pool_id.clone(), AtomicU32::new(0));
 
         // 启动健康监控
         self.health_monitor.start_monitoring(&pool_id).await?;
@@ -72,6 +173,8 @@ impl DistributedPoolManager {
             .ok_or_else(|| DbPoolError::Runtime(format!("连接池不存在: {}", pool_id)))?;
         
         self.pool_configs.remove(pool_id);
+        self.in_flight.remove(pool_id);
+        self.evict_statement_cache(pool_id);
 
         // 关闭连接池
         pool.1.close().await?;
@@ -83,79 +186,212 @@ impl DistributedPoolManager {
         Ok(())
     }
 
-    /// 执行查询
+    /// 执行查询（读路径）：在 `ReadWriteSplit` 策略下会被分流到某个健康副本
+    ///
+    /// 瞬时错误（见 `DbPoolError::is_transient`）会按当前 `retry_policy` 自动重试
+    /// （每次重试都会重新 `get_read_pool`，因此可能落到故障转移/重建后的新连接池上），
+    /// `DbPoolError::Query` 等其它错误直接向上抛出。
     pub async fn execute_query(
         &self,
         pool_id: &str,
         sql: &str,
         params: Option<QueryParams>,
     ) -> Result<polars::frame::DataFrame> {
-        let start_time = Instant::now();
+        let policy = self.retry_policy.read().await.clone();
+        let mut attempt = 0u32;
 
-        // 获取连接池
-        let pool = self.get_pool_with_fallback(pool_id).await?;
+        loop {
+            let start_time = Instant::now();
 
-        // 执行查询
-        let result = pool.execute_query(sql, params).await;
+            // 获取连接池（可能是副本），并计入该连接池的在途查询数
+            let (pool, routed_id) = self.get_read_pool(pool_id).await?;
+            self.bump_in_flight(&routed_id, 1);
 
-        // 记录指标
-        let execution_time = start_time.elapsed();
-        match &result {
-            Ok(_) => {
-                self.metrics_collector.record_query_success(pool_id, execution_time);
-            }
-            Err(e) => {
-                self.metrics_collector.record_query_error(pool_id, execution_time, e);
-                // 检查是否需要故障转移
-                self.handle_query_failure(pool_id, e).await?;
+            // 同一SQL第二次出现时直接走扩展查询协议的execute_prepared，跳过重新解析
+            let result = self
+                .execute_query_prepared(&pool, &routed_id, sql, params.clone())
+                .await;
+            self.bump_in_flight(&routed_id, -1);
+
+            let execution_time = start_time.elapsed();
+            match result {
+                Ok(df) => {
+                    self.metrics_collector.record_query_success(pool_id, execution_time);
+                    return Ok(df);
+                }
+                Err(e) => {
+                    self.metrics_collector.record_query_error(pool_id, execution_time, &e);
+                    self.handle_query_failure(pool_id, &e).await?;
+
+                    if e.is_transient() && attempt + 1 < policy.max_attempts {
+                        self.metrics_collector.record_retry(pool_id);
+                        let delay = policy
+                            .backoff_delay(attempt)
+                            .mul_f64(next_jitter_fraction(&self.jitter_state));
+                        tracing::warn!(
+                            "连接池 {} 查询遇到瞬时错误，{}ms后进行第{}次重试: {}",
+                            pool_id, delay.as_millis(), attempt + 1, e
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+
+                    return Err(e);
+                }
             }
         }
+    }
 
-        result
+    /// 以流式方式执行查询，按 `batch_size` 行切分结果，通过有界channel回传
+    ///
+    /// 消费者每次 `recv().await` 才会被再填充一批，从而提供背压：生产者在
+    /// channel写满前不会继续从连接池拉取更多数据。批次的产生直接委托给
+    /// `DatabasePool::execute_query_stream`的惰性游标实现，结果集不会在
+    /// 这一层被整体物化。
+    pub async fn execute_query_stream(
+        &self,
+        pool_id: &str,
+        sql: &str,
+        params: Option<QueryParams>,
+        batch_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<polars::frame::DataFrame>>> {
+        let (pool, _routed_id) = self.get_read_pool(pool_id).await?;
+        pool.execute_query_stream(sql, params, batch_size).await
     }
 
-    /// 执行非查询操作
+    /// 执行非查询操作（写路径）：始终路由到主库，不会被分流到副本
+    ///
+    /// 重试语义与 [`Self::execute_query`] 一致：仅对`DbPoolError::is_transient`
+    /// 判定为瞬时的错误按 `retry_policy` 自动重试。
     pub async fn execute_non_query(
         &self,
         pool_id: &str,
         sql: &str,
         params: Option<QueryParams>,
     ) -> Result<u64> {
-        let start_time = Instant::now();
+        let policy = self.retry_policy.read().await.clone();
+        let mut attempt = 0u32;
 
-        let pool = self.get_pool_with_fallback(pool_id).await?;
-        let result = pool.execute_non_query(sql, params).await;
+        loop {
+            let start_time = Instant::now();
 
-        let execution_time = start_time.elapsed();
-        match &result {
-            Ok(_) => {
-                self.metrics_collector.record_query_success(pool_id, execution_time);
-            }
-            Err(e) => {
-                self.metrics_collector.record_query_error(pool_id, execution_time, e);
-                self.handle_query_failure(pool_id, e).await?;
+            let pool = self.get_write_pool(pool_id).await?;
+            let result = pool.execute_non_query(sql, params.clone()).await;
+
+            let execution_time = start_time.elapsed();
+            match result {
+                Ok(affected) => {
+                    self.metrics_collector.record_query_success(pool_id, execution_time);
+                    return Ok(affected);
+                }
+                Err(e) => {
+                    self.metrics_collector.record_query_error(pool_id, execution_time, &e);
+                    self.handle_query_failure(pool_id, &e).await?;
+
+                    if e.is_transient() && attempt + 1 < policy.max_attempts {
+                        self.metrics_collector.record_retry(pool_id);
+                        let delay = policy
+                            .backoff_delay(attempt)
+                            .mul_f64(next_jitter_fraction(&self.jitter_state));
+                        tracing::warn!(
+                            "连接池 {} 写操作遇到瞬时错误，{}ms后进行第{}次重试: {}",
+                            pool_id, delay.as_millis(), attempt + 1, e
+                        );
+                        tokio::time::sleep(delay).await;
+                        attempt += 1;
+                        continue;
+                    }
+
+                    return Err(e);
+                }
             }
         }
-
-        result
     }
 
-    /// 批量执行操作
+    /// 批量执行操作（写路径）：始终路由到主库
     pub async fn execute_batch(
         &self,
         pool_id: &str,
         operations: Vec<BatchOperation>,
     ) -> Result<Vec<BatchResult>> {
-        let pool = self.get_pool_with_fallback(pool_id).await?;
+        let pool = self.get_write_pool(pool_id).await?;
         pool.execute_batch(operations).await
     }
 
-    /// 获取连接池状态
+    /// 在单个连接上以事务方式执行一组操作（写路径）：始终路由到主库
+    pub async fn execute_transaction(
+        &self,
+        pool_id: &str,
+        operations: Vec<BatchOperation>,
+    ) -> Result<Vec<BatchResult>> {
+        let pool = self.get_write_pool(pool_id).await?;
+        pool.execute_transaction(operations).await
+    }
+
+    /// 从连接池租用一个连接并开启事务会话，由调用方负责提交/回滚（写路径）
+    pub async fn begin(&self, pool_id: &str) -> Result<Box<dyn TransactionSession>> {
+        let pool = self.get_write_pool(pool_id).await?;
+        pool.begin_session().await
+    }
+
+    /// 在线增量备份（目前仅SQLite后端支持，其它后端返回错误）
+    pub async fn backup_to(&self, pool_id: &str, dest_path: &str, pages_per_step: i32) -> Result<()> {
+        let pool = self.get_write_pool(pool_id).await?;
+        pool.backup_to(dest_path, pages_per_step).await
+    }
+
+    /// 注册标量UDF（目前仅SQLite后端支持，其它后端返回错误）
+    pub async fn register_scalar_function(
+        &self,
+        pool_id: &str,
+        name: &str,
+        arity: i32,
+        func: std::sync::Arc<dyn Fn(Vec<DatabaseValue>) -> Result<DatabaseValue> + Send + Sync>,
+    ) -> Result<()> {
+        let pool = self.get_write_pool(pool_id).await?;
+        pool.register_scalar_function(name, arity, func).await
+    }
+
+    /// 加载数据库扩展（目前仅SQLite后端支持，其它后端返回错误）
+    pub async fn load_extension(&self, pool_id: &str, path: &str) -> Result<()> {
+        let pool = self.get_write_pool(pool_id).await?;
+        pool.load_extension(path).await
+    }
+
+    /// 将DataFrame批量写回目标表（目前仅MSSQL后端支持，其它后端返回错误）
+    pub async fn write_dataframe(
+        &self,
+        pool_id: &str,
+        table: &str,
+        df: &polars::frame::DataFrame,
+        mode: crate::core::types::WriteMode,
+    ) -> Result<u64> {
+        let pool = self.get_write_pool(pool_id).await?;
+        pool.write_dataframe(table, df, mode).await
+    }
+
+    /// 获取一条查询结果集的列结构，不拉取任何行（读路径，目前仅MSSQL后端支持，其它后端返回错误）
+    pub async fn describe(
+        &self,
+        pool_id: &str,
+        sql: &str,
+    ) -> Result<Vec<crate::core::types::ColumnSchema>> {
+        let (pool, _routed_id) = self.get_read_pool(pool_id).await?;
+        pool.describe(sql).await
+    }
+
+    /// 获取连接池状态（叠加熔断器的实时状态与连续失败次数）
     pub async fn get_pool_status(&self, pool_id: &str) -> Result<PoolStatus> {
         let pool = self.local_pools.get(pool_id)
             .ok_or_else(|| DbPoolError::Runtime(format!("连接池不存在: {}", pool_id)))?;
 
-        pool.get_status().await
+        let mut status = pool.get_status().await?;
+        let (circuit_state, consecutive_failures) = self.health_monitor.breaker_snapshot(pool_id);
+        status.circuit_state = circuit_state;
+        status.consecutive_failures = consecutive_failures;
+        status.is_healthy = matches!(circuit_state, CircuitState::Closed);
+        Ok(status)
     }
 
     /// 获取连接池指标
@@ -179,6 +415,48 @@ impl DistributedPoolManager {
         *guard = strategy;
     }
 
+    /// 设置瞬时连接错误的重试策略
+    pub async fn set_retry_policy(&self, policy: RetryPolicy) {
+        let mut guard = self.retry_policy.write().await;
+        *guard = policy;
+    }
+
+    /// 以Prometheus文本暴露格式（text exposition format）渲染所有连接池的指标，
+    /// 供 `/metrics` 端点或外部抓取器使用
+    pub async fn render_prometheus(&self) -> String {
+        let mut out = self.metrics_collector.render_prometheus();
+
+        out.push_str("# HELP dbpool_up 连接池当前是否健康（熔断器处于Closed状态）\n");
+        out.push_str("# TYPE dbpool_up gauge\n");
+        for pool_id in self.list_pools() {
+            let healthy = self.health_monitor.is_pool_healthy(&pool_id).await;
+            out.push_str(&format!(
+                "dbpool_up{{pool_id=\"{}\"}} {}\n",
+                escape_label(&pool_id),
+                if healthy { 1 } else { 0 }
+            ));
+        }
+
+        out.push_str("# HELP dbpool_connection_utilization 连接池使用率（active_connections / total_connections）\n");
+        out.push_str("# TYPE dbpool_connection_utilization gauge\n");
+        for pool_id in self.list_pools() {
+            if let Ok(status) = self.get_pool_status(&pool_id).await {
+                let utilization = if status.total_connections > 0 {
+                    status.active_connections as f64 / status.total_connections as f64
+                } else {
+                    0.0
+                };
+                out.push_str(&format!(
+                    "dbpool_connection_utilization{{pool_id=\"{}\"}} {}\n",
+                    escape_label(&pool_id),
+                    utilization
+                ));
+            }
+        }
+
+        out
+    }
+
     // 私有辅助方法
 
     /// 获取连接池（带故障转移）
@@ -217,7 +495,111 @@ impl DistributedPoolManager {
                 }
                 Err(DbPoolError::Runtime("所有连接池都不可用".to_string()))
             }
+            FailoverStrategy::ReadWriteSplit { .. } => {
+                // 读写分离下主库本身没有备份目标，不健康即直接报错
+                Err(DbPoolError::Runtime(format!("主连接池不可用: {}", pool_id)))
+            }
+        }
+    }
+
+    /// 写路径：始终路由到 `pool_id` 本身（读写分离下即主库），不会分流到副本
+    async fn get_write_pool(&self, pool_id: &str) -> Result<Arc<dyn DatabasePool>> {
+        self.get_pool_with_fallback(pool_id).await
+    }
+
+    /// 对同一 `(routed_pool_id, sql)` 首次出现时调用 `pool.prepare` 取得后端句柄
+    /// 并缓存，此后的重复调用直接走 `pool.execute_prepared`
+    async fn execute_query_prepared(
+        &self,
+        pool: &Arc<dyn DatabasePool>,
+        routed_pool_id: &str,
+        sql: &str,
+        params: Option<QueryParams>,
+    ) -> Result<polars::frame::DataFrame> {
+        let cache_key = PreparedStatementCache::cache_key(routed_pool_id, sql);
+
+        let cached = self.statement_cache.get(&cache_key).map(|h| h.clone());
+        if let Some(handle) = cached {
+            return pool.execute_prepared(&handle, params).await;
         }
+
+        let handle = pool.prepare(sql).await?;
+        let result = pool.execute_prepared(&handle, params).await;
+        self.statement_cache.insert(cache_key, handle);
+        result
+    }
+
+    /// 读路径：在 `ReadWriteSplit` 策略下，若 `pool_id` 是配置的主库，则在健康副本间
+    /// 按策略选择的算法分流；没有健康副本时回退到主库本身。其它策略下等价于
+    /// `get_pool_with_fallback`。返回实际被选中的连接池及其id（用于在途计数等统计）
+    async fn get_read_pool(&self, pool_id: &str) -> Result<(Arc<dyn DatabasePool>, String)> {
+        let strategy = self.failover_strategy.read().await;
+        if let FailoverStrategy::ReadWriteSplit { primary, replicas, algorithm } = &*strategy {
+            if pool_id == primary {
+                let mut healthy_replicas = Vec::new();
+                for replica_id in replicas {
+                    if self.local_pools.contains_key(replica_id)
+                        && self.health_monitor.is_pool_healthy(replica_id).await
+                    {
+                        healthy_replicas.push(replica_id.clone());
+                    }
+                }
+
+                if !healthy_replicas.is_empty() {
+                    let chosen = match algorithm {
+                        LoadBalanceAlgorithm::LeastConnections => healthy_replicas
+                            .iter()
+                            .min_by_key(|id| self.current_in_flight(id))
+                            .cloned(),
+                        _ => {
+                            // RoundRobin/WeightedRoundRobin/Random均按轮询处理，
+                            // 加权与随机策略留待接入真实权重/随机源时再细化
+                            let idx = self.replica_round_robin.fetch_add(1, Ordering::Relaxed);
+                            healthy_replicas.get(idx % healthy_replicas.len()).cloned()
+                        }
+                    };
+
+                    if let Some(replica_id) = chosen {
+                        if let Some(pool) = self.local_pools.get(&replica_id) {
+                            tracing::debug!("读请求分流到副本: {} -> {}", pool_id, replica_id);
+                            let pool = pool.clone();
+                            drop(strategy);
+                            return Ok((pool, replica_id));
+                        }
+                    }
+                }
+
+                tracing::warn!("所有副本均不可用，读请求回退到主库: {}", pool_id);
+            }
+        }
+        drop(strategy);
+
+        let pool = self.get_pool_with_fallback(pool_id).await?;
+        Ok((pool, pool_id.to_string()))
+    }
+
+    /// 读取某连接池当前的在途查询数
+    fn current_in_flight(&self, pool_id: &str) -> u32 {
+        self.in_flight
+            .get(pool_id)
+            .map(|counter| counter.load(Ordering::Relaxed))
+            .unwrap_or(0)
+    }
+
+    /// 调整某连接池的在途查询计数（`delta` 为 +1/-1）
+    fn bump_in_flight(&self, pool_id: &str, delta: i32) {
+        let counter = self.in_flight.entry(pool_id.to_string()).or_insert_with(|| AtomicU32::new(0));
+        if delta >= 0 {
+            counter.fetch_add(delta as u32, Ordering::Relaxed);
+        } else {
+            counter.fetch_sub((-delta) as u32, Ordering::Relaxed);
+        }
+    }
+
+    /// 清除某连接池在 `statement_cache` 中的所有缓存项（连接池被移除/重建时调用）
+    fn evict_statement_cache(&self, pool_id: &str) {
+        let prefix = format!("{}\u{0}", pool_id);
+        self.statement_cache.retain(|key, _| !key.starts_with(&prefix));
     }
 
     /// 处理查询失败
@@ -239,6 +621,19 @@ impl DistributedPoolManager {
                 // 查询错误，记录但不影响连接池状态
                 self.metrics_collector.record_query_error(pool_id, Duration::ZERO, error);
             }
+            DbPoolError::Database { category, .. } => {
+                if *category == crate::core::sqlstate::ErrorCategory::Connection {
+                    // 连接类SQLSTATE（如too_many_connections）与DbPoolError::Connection同等对待
+                    self.health_monitor.mark_unhealthy(pool_id).await;
+
+                    if let Some(config) = self.pool_configs.get(pool_id) {
+                        self.recreate_pool(pool_id, config.value().clone()).await?;
+                    }
+                } else {
+                    // 完整性约束/语法/鉴权/瞬时错误都只记录，不认为连接池本身不健康
+                    self.metrics_collector.record_query_error(pool_id, Duration::ZERO, error);
+                }
+            }
             _ => {
                 // 其他错误
                 tracing::warn!("未处理的错误类型: {}", error);
@@ -256,6 +651,8 @@ impl DistributedPoolManager {
         if let Some((_, old_pool)) = self.local_pools.remove(pool_id) {
             let _ = old_pool.close().await;
         }
+        // 旧连接池上缓存的后端预编译句柄随之失效
+        self.evict_statement_cache(pool_id);
 
         // 创建新连接池
         let new_pool = DatabaseFactory::create_pool(&config).await?;
@@ -269,6 +666,83 @@ impl DistributedPoolManager {
     }
 }
 
+/// 对数分桶延迟直方图
+///
+/// 桶的上界按几何级数增长（`min_micros * factor^i`），最后一桶代表“+∞”。
+/// 相比保留每次采样的原始数据，这里只需要O(桶数)的内存就能估算任意分位数，
+/// 代价是分位数结果被量化到所在桶的边界（可选线性插值）。
+const HISTOGRAM_BUCKETS: usize = 64;
+const HISTOGRAM_MIN_MICROS: f64 = 1.0;
+const HISTOGRAM_FACTOR: f64 = 1.5;
+
+#[derive(Debug, Clone)]
+struct LatencyHistogram {
+    counts: [u64; HISTOGRAM_BUCKETS],
+}
+
+impl LatencyHistogram {
+    fn new() -> Self {
+        Self {
+            counts: [0; HISTOGRAM_BUCKETS],
+        }
+    }
+
+    /// 根据耗时计算所在桶下标
+    fn bucket_index(duration: Duration) -> usize {
+        let micros = (duration.as_secs_f64() * 1_000_000.0).max(HISTOGRAM_MIN_MICROS);
+        let index = ((micros / HISTOGRAM_MIN_MICROS).ln() / HISTOGRAM_FACTOR.ln()).floor();
+        if index.is_nan() || index < 0.0 {
+            0
+        } else {
+            (index as usize).min(HISTOGRAM_BUCKETS - 1)
+        }
+    }
+
+    /// 桶下标对应的耗时上界
+    fn bucket_upper_bound(index: usize) -> Duration {
+        let micros = HISTOGRAM_MIN_MICROS * HISTOGRAM_FACTOR.powi(index as i32 + 1);
+        Duration::from_secs_f64(micros / 1_000_000.0)
+    }
+
+    fn record(&mut self, duration: Duration) {
+        self.counts[Self::bucket_index(duration)] += 1;
+    }
+
+    /// 每个桶的耗时上界（秒）与累计计数，用于渲染Prometheus histogram的
+    /// `le=` 桶（不含末尾的 `+Inf` 桶，调用方自行补充）
+    fn cumulative_seconds_buckets(&self) -> Vec<(f64, u64)> {
+        let mut cumulative = 0u64;
+        self.counts
+            .iter()
+            .enumerate()
+            .map(|(i, count)| {
+                cumulative += count;
+                (Self::bucket_upper_bound(i).as_secs_f64(), cumulative)
+            })
+            .collect()
+    }
+
+    /// 估算分位数 `q`（0.0~1.0）对应的耗时：累加桶计数直至达到目标比例，
+    /// 返回该桶的耗时上界
+    fn quantile(&self, q: f64) -> Duration {
+        let total: u64 = self.counts.iter().sum();
+        if total == 0 {
+            return Duration::ZERO;
+        }
+
+        let target = (total as f64 * q).ceil() as u64;
+        let mut cumulative: u64 = 0;
+        for (i, count) in self.counts.iter().enumerate() {
+            cumulative += count;
+            if cumulative >= target {
+                return Self::bucket_upper_bound(i);
+            }
+        }
+
+        Self::bucket_upper_bound(HISTOGRAM_BUCKETS - 1)
+    }
+}
+
 /// 指标收集器
 pub struct MetricsCollector {
     pool_metrics: DashMap<String, PoolMetricsData>,
@@ -278,7 +752,9 @@ pub struct MetricsCollector {
 struct PoolMetricsData {
     total_queries: u64,
     total_errors: u64,
+    total_retries: u64,
     total_execution_time: Duration,
+    latency_histogram: LatencyHistogram,
     last_query_time: Option<Instant>,
     created_at: Instant,
 }
@@ -296,7 +772,9 @@ impl MetricsCollector {
             PoolMetricsData {
                 total_queries: 0,
                 total_errors: 0,
+                total_retries: 0,
                 total_execution_time: Duration::ZERO,
+                latency_histogram: LatencyHistogram::new(),
                 last_query_time: None,
                 created_at: Instant::now(),
             },
@@ -307,10 +785,18 @@ impl MetricsCollector {
         self.pool_metrics.remove(pool_id);
     }
 
+    /// 记录一次因瞬时连接错误触发的自动重试
+    pub fn record_retry(&self, pool_id: &str) {
+        if let Some(mut metrics) = self.pool_metrics.get_mut(pool_id) {
+            metrics.total_retries += 1;
+        }
+    }
+
     pub fn record_query_success(&self, pool_id: &str, execution_time: Duration) {
         if let Some(mut metrics) = self.pool_metrics.get_mut(pool_id) {
             metrics.total_queries += 1;
             metrics.total_execution_time += execution_time;
+            metrics.latency_histogram.record(execution_time);
             metrics.last_query_time = Some(Instant::now());
         }
     }
@@ -320,6 +806,7 @@ impl MetricsCollector {
             metrics.total_queries += 1;
             metrics.total_errors += 1;
             metrics.total_execution_time += execution_time;
+            metrics.latency_histogram.record(execution_time);
             metrics.last_query_time = Some(Instant::now());
         }
     }
@@ -352,64 +839,239 @@ impl MetricsCollector {
             queries_per_second: qps,
             connection_utilization: 0.0, // 需要从实际连接池获取
             avg_query_time,
-            p99_query_time: avg_query_time, // 简化实现
+            p50_query_time: data.latency_histogram.quantile(0.50),
+            p99_query_time: data.latency_histogram.quantile(0.99),
+            p999_query_time: data.latency_histogram.quantile(0.999),
             error_rate,
             total_queries: data.total_queries,
             total_errors: data.total_errors,
             cache_hit_rate: 0.0, // 暂未实现缓存
+            total_retries: data.total_retries,
         })
     }
+
+    /// 渲染所有连接池的查询计数、错误计数与延迟直方图为Prometheus文本暴露格式
+    ///
+    /// `dbpool_up`/`dbpool_connection_utilization` 依赖连接池与熔断器实时状态，
+    /// 不在 `MetricsCollector` 的管辖范围内，由调用方（`DistributedPoolManager`）补充
+    pub fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+
+        out.push_str("# HELP dbpool_queries_total 累计执行的查询数量\n");
+        out.push_str("# TYPE dbpool_queries_total counter\n");
+        for entry in self.pool_metrics.iter() {
+            out.push_str(&format!(
+                "dbpool_queries_total{{pool_id=\"{}\"}} {}\n",
+                escape_label(entry.key()),
+                entry.value().total_queries
+            ));
+        }
+
+        out.push_str("# HELP dbpool_errors_total 累计查询失败数量\n");
+        out.push_str("# TYPE dbpool_errors_total counter\n");
+        for entry in self.pool_metrics.iter() {
+            out.push_str(&format!(
+                "dbpool_errors_total{{pool_id=\"{}\"}} {}\n",
+                escape_label(entry.key()),
+                entry.value().total_errors
+            ));
+        }
+
+        out.push_str("# HELP dbpool_query_duration_seconds 查询耗时分布\n");
+        out.push_str("# TYPE dbpool_query_duration_seconds histogram\n");
+        for entry in self.pool_metrics.iter() {
+            let pool_id = escape_label(entry.key());
+            let data = entry.value();
+            let mut total: u64 = 0;
+            for (le, cumulative) in data.latency_histogram.cumulative_seconds_buckets() {
+                total = cumulative;
+                out.push_str(&format!(
+                    "dbpool_query_duration_seconds_bucket{{pool_id=\"{}\",le=\"{}\"}} {}\n",
+                    pool_id, le, cumulative
+                ));
+            }
+            out.push_str(&format!(
+                "dbpool_query_duration_seconds_bucket{{pool_id=\"{}\",le=\"+Inf\"}} {}\n",
+                pool_id, total
+            ));
+            out.push_str(&format!(
+                "dbpool_query_duration_seconds_sum{{pool_id=\"{}\"}} {}\n",
+                pool_id,
+                data.total_execution_time.as_secs_f64()
+            ));
+            out.push_str(&format!(
+                "dbpool_query_duration_seconds_count{{pool_id=\"{}\"}} {}\n",
+                pool_id, total
+            ));
+        }
+
+        out
+    }
 }
 
-/// 健康监控器
+/// Prometheus标签值转义：反斜杠、双引号与换行需要按文本暴露格式要求转义
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// 熔断器连续失败阈值：达到该次数后从 Closed 切换到 Open
+const CIRCUIT_FAILURE_THRESHOLD: u32 = 3;
+/// Open状态的冷却时长：超过该时长后才允许下一次试探（Half-Open）
+const CIRCUIT_OPEN_COOLDOWN: Duration = Duration::from_secs(30);
+/// 周期性探测间隔
+const PROBE_INTERVAL: Duration = Duration::from_secs(30);
+
+/// 单个连接池的熔断器内部状态（不对外暴露，`breaker_snapshot`返回其只读快照）
+#[derive(Debug, Clone)]
+struct CircuitBreakerState {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl CircuitBreakerState {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+
+    /// 冷却是否已结束（只有Open状态下才有意义）
+    fn cooldown_elapsed(&self) -> bool {
+        self.opened_at
+            .map(|t| t.elapsed() >= CIRCUIT_OPEN_COOLDOWN)
+            .unwrap_or(true)
+    }
+
+    fn record_success(&mut self) {
+        self.state = CircuitState::Closed;
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= CIRCUIT_FAILURE_THRESHOLD {
+            self.state = CircuitState::Open;
+            self.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+/// 健康监控器：周期性调用每个连接池的 `health_check()`，并维护一套
+/// Closed → Open → Half-Open → (Closed | Open) 的熔断器状态机。
+///
+/// - Closed：正常放行；连续失败达到 `CIRCUIT_FAILURE_THRESHOLD` 次后转为 Open。
+/// - Open：在冷却窗口 `CIRCUIT_OPEN_COOLDOWN` 内直接判定为不健康，不再探测。
+/// - Half-Open：冷却结束后的下一次周期性探测即为“单次试探”，成功则回到
+///   Closed，失败则重新回到 Open 并重置冷却计时。
 pub struct HealthMonitor {
-    pool_health_status: DashMap<String, bool>,
+    /// 与 `DistributedPoolManager::local_pools` 共享的同一份连接池map
+    pools: Arc<DashMap<String, Arc<dyn DatabasePool>>>,
+    breakers: Arc<DashMap<String, CircuitBreakerState>>,
+    monitoring_tasks: DashMap<String, tokio::task::JoinHandle<()>>,
 }
 
 impl HealthMonitor {
-    pub fn new() -> Self {
+    pub fn new(pools: Arc<DashMap<String, Arc<dyn DatabasePool>>>) -> Self {
         Self {
-            pool_health_status: DashMap::new(),
+            pools,
+            breakers: Arc::new(DashMap::new()),
+            monitoring_tasks: DashMap::new(),
         }
     }
 
     pub async fn start_monitoring(&self, pool_id: &str) -> Result<()> {
-        self.pool_health_status.insert(pool_id.to_string(), true);
-        
-        // 启动定期健康检查
+        self.breakers.insert(pool_id.to_string(), CircuitBreakerState::new());
+
         let pool_id = pool_id.to_string();
-        let health_status = self.pool_health_status.clone();
-        
-        tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(30));
+        let pools = self.pools.clone();
+        let breakers = self.breakers.clone();
+
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(PROBE_INTERVAL);
+            interval.tick().await; // 首个tick立即触发，跳过以避免冷启动时的多余探测
+
             loop {
                 interval.tick().await;
-                // 这里应该执行实际的健康检查逻辑
-                // 暂时保持健康状态不变
+
+                let Some(pool) = pools.get(&pool_id).map(|p| p.clone()) else {
+                    break; // 连接池已被移除，停止探测
+                };
+
+                // Open状态下冷却未结束时跳过探测，避免持续打挂一个已知不健康的后端
+                if let Some(breaker) = breakers.get(&pool_id) {
+                    if breaker.state == CircuitState::Open && !breaker.cooldown_elapsed() {
+                        continue;
+                    }
+                }
+
+                // 冷却结束后的这一次探测即为Half-Open的单次试探
+                if let Some(mut breaker) = breakers.get_mut(&pool_id) {
+                    if breaker.state == CircuitState::Open {
+                        breaker.state = CircuitState::HalfOpen;
+                    }
+                }
+
+                let probe_ok = pool.health_check().await.unwrap_or(false);
+                if let Some(mut breaker) = breakers.get_mut(&pool_id) {
+                    if probe_ok {
+                        breaker.record_success();
+                    } else {
+                        breaker.record_failure();
+                    }
+                }
             }
         });
 
+        self.monitoring_tasks.insert(pool_id, handle);
+
         Ok(())
     }
 
     pub async fn stop_monitoring(&self, pool_id: &str) -> Result<()> {
-        self.pool_health_status.remove(pool_id);
+        if let Some((_, handle)) = self.monitoring_tasks.remove(pool_id) {
+            handle.abort();
+        }
+        self.breakers.remove(pool_id);
         Ok(())
     }
 
     pub async fn check_pool_health(&self, pool_id: &str) -> Result<bool> {
-        Ok(self.pool_health_status.get(pool_id).map(|v| *v).unwrap_or(false))
+        Ok(self.is_pool_healthy(pool_id).await)
     }
 
     pub async fn is_pool_healthy(&self, pool_id: &str) -> bool {
-        self.pool_health_status.get(pool_id).map(|v| *v).unwrap_or(false)
+        self.breakers
+            .get(pool_id)
+            .map(|b| b.state == CircuitState::Closed)
+            .unwrap_or(false)
+    }
+
+    /// 熔断器当前状态与连续失败次数的只读快照，供 `get_pool_status` 使用
+    pub fn breaker_snapshot(&self, pool_id: &str) -> (CircuitState, u32) {
+        self.breakers
+            .get(pool_id)
+            .map(|b| (b.state, b.consecutive_failures))
+            .unwrap_or((CircuitState::Closed, 0))
     }
 
     pub async fn mark_healthy(&self, pool_id: &str) {
-        self.pool_health_status.insert(pool_id.to_string(), true);
+        self.breakers
+            .entry(pool_id.to_string())
+            .or_insert_with(CircuitBreakerState::new)
+            .record_success();
     }
 
     pub async fn mark_unhealthy(&self, pool_id: &str) {
-        self.pool_health_status.insert(pool_id.to_string(), false);
+        self.breakers
+            .entry(pool_id.to_string())
+            .or_insert_with(CircuitBreakerState::new)
+            .record_failure();
     }
 }
\ No newline at end of file
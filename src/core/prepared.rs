@@ -0,0 +1,157 @@
+use crate::core::error::Result;
+use crate::core::types::DatabaseValue;
+use dashmap::DashMap;
+use polars::frame::DataFrame;
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// 参数的编码格式（类比Postgres扩展查询协议的文本/二进制格式码）
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamFormat {
+    Text,
+    Binary,
+}
+
+impl Default for ParamFormat {
+    fn default() -> Self {
+        ParamFormat::Text
+    }
+}
+
+/// 预编译语句的元数据：参数与结果列的推断类型
+#[derive(Debug, Clone, Default)]
+pub struct PreparedStatementMeta {
+    pub sql: String,
+    /// 参数类型OID（使用 `DatabaseValue` 判别值充当简化OID）
+    pub param_type_oids: Vec<i32>,
+    /// 结果列类型OID
+    pub column_type_oids: Vec<i32>,
+    pub column_names: Vec<String>,
+}
+
+/// 按SQL文本缓存已解析/描述过的语句，避免重复解析
+pub struct PreparedStatementCache {
+    entries: DashMap<String, PreparedStatementMeta>,
+}
+
+impl PreparedStatementCache {
+    pub fn new() -> Self {
+        Self {
+            entries: DashMap::new(),
+        }
+    }
+
+    pub fn cache_key(pool_id: &str, sql: &str) -> String {
+        format!("{}\u{0}{}", pool_id, sql)
+    }
+
+    pub fn get_or_insert(&self, key: &str, sql: &str) -> PreparedStatementMeta {
+        self.entries
+            .entry(key.to_string())
+            .or_insert_with(|| PreparedStatementMeta {
+                sql: sql.to_string(),
+                ..Default::default()
+            })
+            .clone()
+    }
+
+    pub fn update_from_dataframe(&self, key: &str, df: &DataFrame) {
+        if let Some(mut meta) = self.entries.get_mut(key) {
+            meta.column_names = df
+                .get_column_names()
+                .iter()
+                .map(|s| s.to_string())
+                .collect();
+            meta.column_type_oids = df
+                .dtypes()
+                .iter()
+                .map(Self::polars_dtype_to_oid)
+                .collect();
+        }
+    }
+
+    pub fn update_param_types(&self, key: &str, params: &HashMap<String, DatabaseValue>) {
+        if let Some(mut meta) = self.entries.get_mut(key) {
+            meta.param_type_oids = params
+                .values()
+                .map(Self::database_value_to_oid)
+                .collect();
+        }
+    }
+
+    fn polars_dtype_to_oid(dtype: &polars::prelude::DataType) -> i32 {
+        use polars::prelude::DataType;
+        match dtype {
+            DataType::Boolean => 16,
+            DataType::Int32 => 23,
+            DataType::Int64 => 20,
+            DataType::Float32 => 700,
+            DataType::Float64 => 701,
+            DataType::String => 25,
+            DataType::Binary => 17,
+            DataType::Datetime(_, _) => 1114,
+            _ => 0,
+        }
+    }
+
+    fn database_value_to_oid(value: &DatabaseValue) -> i32 {
+        match value {
+            DatabaseValue::Null => 0,
+            DatabaseValue::Bool(_) => 16,
+            DatabaseValue::I32(_) => 23,
+            DatabaseValue::I64(_) => 20,
+            DatabaseValue::F32(_) => 700,
+            DatabaseValue::F64(_) => 701,
+            DatabaseValue::String(_) => 25,
+            DatabaseValue::Bytes(_) => 17,
+            DatabaseValue::DateTime(_) => 1114,
+            DatabaseValue::Uuid(_) => 2950,
+            DatabaseValue::Decimal(_) => 1700,
+            DatabaseValue::Date(_) => 1082,
+            DatabaseValue::Time(_) => 1083,
+            DatabaseValue::DateTimeTz(_) => 1184,
+        }
+    }
+}
+
+impl Default for PreparedStatementCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// 一条预编译语句的句柄，可重复执行
+#[derive(Clone)]
+pub struct PreparedStatementHandle {
+    pub pool_id: String,
+    pub sql: String,
+    pub cache_key: String,
+    /// 按参数位置固定声明的类型OID（覆盖自动推断）
+    pub pinned_param_types: Arc<Mutex<HashMap<usize, i32>>>,
+    /// 按参数位置固定声明的编码格式
+    pub param_formats: Arc<Mutex<HashMap<usize, ParamFormat>>>,
+}
+
+impl PreparedStatementHandle {
+    pub fn new(pool_id: String, sql: String) -> Self {
+        let cache_key = PreparedStatementCache::cache_key(&pool_id, &sql);
+        Self {
+            pool_id,
+            sql,
+            cache_key,
+            pinned_param_types: Arc::new(Mutex::new(HashMap::new())),
+            param_formats: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    pub async fn pin_param_type(&self, index: usize, type_oid: i32) {
+        self.pinned_param_types.lock().await.insert(index, type_oid);
+    }
+
+    pub async fn set_param_format(&self, index: usize, format: ParamFormat) {
+        self.param_formats.lock().await.insert(index, format);
+    }
+}
+
+pub type PreparedResult<T> = Result<T>;
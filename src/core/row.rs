@@ -0,0 +1,95 @@
+use crate::core::error::{ConversionError, DbPoolError, Result};
+use crate::core::types::DatabaseValue;
+use crate::databases::traits::DatabaseRow;
+use chrono::{DateTime, Utc};
+use uuid::Uuid;
+
+/// 将查询结果行反序列化为具体Rust类型，供 `DatabasePool::query_as` 使用
+///
+/// 列按位置（而非列名）拉取，与元组的字段顺序一一对应；已为1~8元的元组提供
+/// 了位置化的blanket实现，更复杂的结构目前需要手写实现（后续可提供派生宏）。
+pub trait FromRow: Sized {
+    fn from_row(row: &dyn DatabaseRow) -> Result<Self>;
+}
+
+/// 从单个 `DatabaseValue` 拉取指定位置的列，转换失败时报告列下标
+fn column<T>(row: &dyn DatabaseRow, index: usize) -> Result<T>
+where
+    T: TryFrom<DatabaseValue, Error = DbPoolError>,
+{
+    let value = row.get_value(index).unwrap_or(DatabaseValue::Null);
+    T::try_from(value).map_err(|e| {
+        DbPoolError::DataConversion(ConversionError::TypeConversion(format!(
+            "第{}列: {}",
+            index, e
+        )))
+    })
+}
+
+macro_rules! impl_try_from_database_value {
+    ($ty:ty, $variant:ident) => {
+        impl TryFrom<DatabaseValue> for $ty {
+            type Error = DbPoolError;
+
+            fn try_from(value: DatabaseValue) -> Result<Self> {
+                match value {
+                    DatabaseValue::$variant(v) => Ok(v),
+                    other => Err(DbPoolError::DataConversion(ConversionError::TypeConversion(
+                        format!("无法将 {:?} 转换为 {}", other, stringify!($ty)),
+                    ))),
+                }
+            }
+        }
+    };
+}
+
+impl_try_from_database_value!(bool, Bool);
+impl_try_from_database_value!(i32, I32);
+impl_try_from_database_value!(i64, I64);
+impl_try_from_database_value!(f32, F32);
+impl_try_from_database_value!(f64, F64);
+impl_try_from_database_value!(String, String);
+impl_try_from_database_value!(Vec<u8>, Bytes);
+impl_try_from_database_value!(DateTime<Utc>, DateTime);
+impl_try_from_database_value!(Uuid, Uuid);
+impl_try_from_database_value!(rust_decimal::Decimal, Decimal);
+impl_try_from_database_value!(chrono::NaiveDate, Date);
+impl_try_from_database_value!(chrono::NaiveTime, Time);
+impl_try_from_database_value!(DateTime<chrono::FixedOffset>, DateTimeTz);
+
+/// `Option<T>` 将 `Null` 视为 `None`，其余值委托给 `T` 的转换
+impl<T> TryFrom<DatabaseValue> for Option<T>
+where
+    T: TryFrom<DatabaseValue, Error = DbPoolError>,
+{
+    type Error = DbPoolError;
+
+    fn try_from(value: DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Null => Ok(None),
+            other => T::try_from(other).map(Some),
+        }
+    }
+}
+
+macro_rules! impl_from_row_for_tuple {
+    ($($idx:tt => $ty:ident),+) => {
+        impl<$($ty),+> FromRow for ($($ty,)+)
+        where
+            $($ty: TryFrom<DatabaseValue, Error = DbPoolError>,)+
+        {
+            fn from_row(row: &dyn DatabaseRow) -> Result<Self> {
+                Ok(($(column::<$ty>(row, $idx)?,)+))
+            }
+        }
+    };
+}
+
+impl_from_row_for_tuple!(0 => T0);
+impl_from_row_for_tuple!(0 => T0, 1 => T1);
+impl_from_row_for_tuple!(0 => T0, 1 => T1, 2 => T2);
+impl_from_row_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3);
+impl_from_row_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4);
+impl_from_row_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5);
+impl_from_row_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6);
+impl_from_row_for_tuple!(0 => T0, 1 => T1, 2 => T2, 3 => T3, 4 => T4, 5 => T5, 6 => T6, 7 => T7);
@@ -0,0 +1,199 @@
+/// 标准SQL的SQLSTATE错误码，按ANSI SQL/PostgreSQL的errcodes表分类
+///
+/// 不是PostgreSQL专属概念——SQLSTATE是ANSI SQL标准定义的五字符错误码，
+/// 各后端（MSSQL的`TokenError`也带有类似但专属于TDS协议的错误号，见
+/// `databases::mssql::error::SqlServerError`）理论上都可能产出或映射到这一套码。
+/// 放在core而非某个具体后端下，便于`DbPoolError::Database`在不依赖任何
+/// 后端feature的情况下持有这个类型。
+///
+/// 只收录常见的错误类，未覆盖的码一律落入`Other`，不追求穷举官方errcodes.txt
+/// 里的全部约400个条目。
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlState {
+    // Class 08 — Connection Exception
+    ConnectionException,
+    ConnectionDoesNotExist,
+    ConnectionFailure,
+    SqlclientUnableToEstablishSqlconnection,
+    SqlserverRejectedEstablishmentOfSqlconnection,
+
+    // Class 23 — Integrity Constraint Violation
+    IntegrityConstraintViolation,
+    RestrictViolation,
+    NotNullViolation,
+    ForeignKeyViolation,
+    UniqueViolation,
+    CheckViolation,
+
+    // Class 28 — Invalid Authorization Specification
+    InvalidAuthorizationSpecification,
+    InvalidPassword,
+
+    // Class 40 — Transaction Rollback
+    TransactionRollback,
+    SerializationFailure,
+    TransactionIntegrityConstraintViolation,
+    StatementCompletionUnknown,
+    DeadlockDetected,
+
+    // Class 42 — Syntax Error or Access Rule Violation
+    SyntaxError,
+    InsufficientPrivilege,
+    UndefinedTable,
+    UndefinedColumn,
+    UndefinedFunction,
+    DuplicateTable,
+
+    // Class 53 — Insufficient Resources
+    InsufficientResources,
+    DiskFull,
+    OutOfMemory,
+    TooManyConnections,
+
+    // Class 55 — Object Not In Prerequisite State
+    LockNotAvailable,
+
+    // Class 57 — Operator Intervention
+    QueryCanceled,
+    AdminShutdown,
+    CrashShutdown,
+    CannotConnectNow,
+
+    /// 未收录的五字符码，原样保留供日志/告警查阅
+    Other(String),
+}
+
+impl SqlState {
+    /// 五字符SQLSTATE码到变体的静态映射
+    ///
+    /// 用match而非哈希表实现：变体数量有限，`rustc`会将其编译为跳转表/
+    /// 二分查找，效果等同于一张静态完美哈希表，且不需要额外引入`phf`依赖。
+    pub fn from_code(code: &str) -> Self {
+        match code {
+            "08000" => Self::ConnectionException,
+            "08003" => Self::ConnectionDoesNotExist,
+            "08006" => Self::ConnectionFailure,
+            "08001" => Self::SqlclientUnableToEstablishSqlconnection,
+            "08004" => Self::SqlserverRejectedEstablishmentOfSqlconnection,
+
+            "23000" => Self::IntegrityConstraintViolation,
+            "23001" => Self::RestrictViolation,
+            "23502" => Self::NotNullViolation,
+            "23503" => Self::ForeignKeyViolation,
+            "23505" => Self::UniqueViolation,
+            "23514" => Self::CheckViolation,
+
+            "28000" => Self::InvalidAuthorizationSpecification,
+            "28P01" => Self::InvalidPassword,
+
+            "40000" => Self::TransactionRollback,
+            "40001" => Self::SerializationFailure,
+            "40002" => Self::TransactionIntegrityConstraintViolation,
+            "40003" => Self::StatementCompletionUnknown,
+            "40P01" => Self::DeadlockDetected,
+
+            "42601" => Self::SyntaxError,
+            "42501" => Self::InsufficientPrivilege,
+            "42P01" => Self::UndefinedTable,
+            "42703" => Self::UndefinedColumn,
+            "42883" => Self::UndefinedFunction,
+            "42P07" => Self::DuplicateTable,
+
+            "53000" => Self::InsufficientResources,
+            "53100" => Self::DiskFull,
+            "53200" => Self::OutOfMemory,
+            "53300" => Self::TooManyConnections,
+
+            "55P03" => Self::LockNotAvailable,
+
+            "57014" => Self::QueryCanceled,
+            "57P01" => Self::AdminShutdown,
+            "57P02" => Self::CrashShutdown,
+            "57P03" => Self::CannotConnectNow,
+
+            other => Self::Other(other.to_string()),
+        }
+    }
+
+    /// 归入粗粒度类别，供调用方判断是否可重试而不必记住具体错误码
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            Self::ConnectionException
+            | Self::ConnectionDoesNotExist
+            | Self::ConnectionFailure
+            | Self::SqlclientUnableToEstablishSqlconnection
+            | Self::SqlserverRejectedEstablishmentOfSqlconnection
+            | Self::TooManyConnections
+            | Self::CannotConnectNow => ErrorCategory::Connection,
+
+            Self::IntegrityConstraintViolation
+            | Self::RestrictViolation
+            | Self::NotNullViolation
+            | Self::ForeignKeyViolation
+            | Self::UniqueViolation
+            | Self::CheckViolation => ErrorCategory::Integrity,
+
+            Self::InvalidAuthorizationSpecification | Self::InvalidPassword | Self::InsufficientPrivilege => {
+                ErrorCategory::Authorization
+            }
+
+            // 序列化失败/死锁/锁等待超时/被取消——重新发起同一操作通常就能成功
+            Self::TransactionRollback
+            | Self::SerializationFailure
+            | Self::TransactionIntegrityConstraintViolation
+            | Self::StatementCompletionUnknown
+            | Self::DeadlockDetected
+            | Self::LockNotAvailable
+            | Self::QueryCanceled
+            | Self::InsufficientResources
+            | Self::OutOfMemory
+            | Self::AdminShutdown
+            | Self::CrashShutdown => ErrorCategory::Transient,
+
+            Self::SyntaxError
+            | Self::UndefinedTable
+            | Self::UndefinedColumn
+            | Self::UndefinedFunction
+            | Self::DuplicateTable => ErrorCategory::Syntax,
+
+            // 磁盘满重试也无济于事，与其它"资源不足"类错误区分开
+            Self::DiskFull => ErrorCategory::Other,
+
+            Self::Other(_) => ErrorCategory::Other,
+        }
+    }
+}
+
+impl std::fmt::Display for SqlState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Other(code) => write!(f, "{}", code),
+            other => write!(f, "{:?}", other),
+        }
+    }
+}
+
+/// 粗粒度错误类别，供调用方（如`AlertManager`/重试逻辑）分流处理，
+/// 不必关心具体的SQLSTATE五字符码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCategory {
+    /// 连接建立/保活失败，通常意味着连接池需要重建
+    Connection,
+    /// 违反完整性约束（唯一键/外键/非空/检查约束），重试同一操作不会成功
+    Integrity,
+    /// 序列化失败/死锁/锁等待超时等——重新发起同一操作通常就能成功
+    Transient,
+    /// SQL语法错误或引用了不存在的对象，重试没有意义
+    Syntax,
+    /// 鉴权/权限不足
+    Authorization,
+    /// 未归类的其它错误
+    Other,
+}
+
+/// 从原始SQLSTATE五字符码分类出`SqlState`与其粗粒度类别
+pub fn classify(code: &str) -> (SqlState, ErrorCategory) {
+    let sqlstate = SqlState::from_code(code);
+    let category = sqlstate.category();
+    (sqlstate, category)
+}
@@ -0,0 +1,194 @@
+//! 把`SslConfig`里声明式的`ssl_mode`/`trust_server_certificate`/`certificate_path`
+//! 解析成一条可执行的TLS校验策略，并在此基础上构造rustls `ClientConfig`。
+//!
+//! 连接器（目前是PostgreSQL后端）只需要调用[`resolve_policy`]拿到策略、再调用
+//! [`build_client_config`]拿到`Arc<rustls::ClientConfig>`，不需要自己判断
+//! `trust_server_certificate`和各个`SslMode`分支之间的优先级关系。
+
+use crate::core::error::{ConfigError, Result};
+use crate::core::types::{SslConfig, SslMode};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, CertificateError, ClientConfig, Error as RustlsError, RootCertStore, ServerName};
+use std::fs::File;
+use std::io::BufReader;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+/// 解析后的TLS校验策略，屏蔽掉`SslConfig`原始字段之间的组合判断
+#[derive(Debug, Clone, PartialEq)]
+pub enum TlsVerificationPolicy {
+    /// 不建立TLS
+    Disabled,
+    /// 加密但不校验证书：`trust_server_certificate=true`，或`Require`/`Prefer`
+    /// 未提供CA时的尽力加密降级
+    AcceptAny,
+    /// 校验证书链由`ca_bundle_path`指定的CA签发，但不比对主机名
+    VerifyCa { ca_bundle_path: String },
+    /// 校验证书链与主机名，标准TLS客户端行为
+    VerifyFull { ca_bundle_path: String },
+}
+
+/// 依据[`SslConfig`]解析出应当采用的校验策略；不做IO，只做字段层面的决策
+pub fn resolve_policy(ssl_config: &SslConfig) -> Result<TlsVerificationPolicy> {
+    if matches!(ssl_config.ssl_mode, SslMode::Disable) {
+        return Ok(TlsVerificationPolicy::Disabled);
+    }
+
+    if ssl_config.trust_server_certificate {
+        return Ok(TlsVerificationPolicy::AcceptAny);
+    }
+
+    match &ssl_config.ssl_mode {
+        SslMode::Disable => unreachable!("已在上面提前返回"),
+        SslMode::Require | SslMode::Prefer => match &ssl_config.certificate_path {
+            Some(path) => Ok(TlsVerificationPolicy::VerifyFull {
+                ca_bundle_path: path.clone(),
+            }),
+            // 未提供CA时，Require/Prefer仍然加密传输，只是无法校验证书来源
+            None => Ok(TlsVerificationPolicy::AcceptAny),
+        },
+        SslMode::VerifyCa => {
+            let ca_bundle_path = ssl_config.certificate_path.clone().ok_or_else(|| {
+                ConfigError::MissingRequired("SslMode::VerifyCa需要certificate_path".to_string())
+            })?;
+            Ok(TlsVerificationPolicy::VerifyCa { ca_bundle_path })
+        }
+        SslMode::VerifyFull => {
+            let ca_bundle_path = ssl_config.certificate_path.clone().ok_or_else(|| {
+                ConfigError::MissingRequired("SslMode::VerifyFull需要certificate_path".to_string())
+            })?;
+            Ok(TlsVerificationPolicy::VerifyFull { ca_bundle_path })
+        }
+    }
+}
+
+/// 依据解析出的策略构造rustls `ClientConfig`，供各后端的TLS连接器复用
+pub fn build_client_config(policy: &TlsVerificationPolicy) -> Result<Arc<ClientConfig>> {
+    let verifier: Arc<dyn ServerCertVerifier> = match policy {
+        TlsVerificationPolicy::Disabled => {
+            return Err(ConfigError::ValidationFailed(
+                "TlsVerificationPolicy::Disabled不应构造TLS ClientConfig".to_string(),
+            )
+            .into());
+        }
+        TlsVerificationPolicy::AcceptAny => Arc::new(AcceptAnyVerifier),
+        TlsVerificationPolicy::VerifyCa { ca_bundle_path } => {
+            Arc::new(CaBundleVerifier::load(ca_bundle_path, false)?)
+        }
+        TlsVerificationPolicy::VerifyFull { ca_bundle_path } => {
+            Arc::new(CaBundleVerifier::load(ca_bundle_path, true)?)
+        }
+    };
+
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_custom_certificate_verifier(verifier)
+        .with_no_client_auth();
+
+    Ok(Arc::new(config))
+}
+
+/// 面向公网HTTPS端点（如`utils::alert_sinks::WebhookSink`要回调的Slack/PagerDuty）
+/// 的标准证书校验`ClientConfig`：用`webpki-roots`内置的公共CA集合校验，不要求
+/// 调用方提供CA证书
+///
+/// 与[`build_client_config`]按[`SslConfig`]解析出的策略（自定义CA包/禁用校验）
+/// 服务于数据库连接不同，这里走的是普通HTTPS客户端该走的标准校验路径
+pub fn default_roots_client_config() -> Arc<ClientConfig> {
+    let mut roots = RootCertStore::empty();
+    roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|ta| {
+        rustls::OwnedTrustAnchor::from_subject_spki_name_constraints(
+            ta.subject,
+            ta.spki,
+            ta.name_constraints,
+        )
+    }));
+
+    let config = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+
+    Arc::new(config)
+}
+
+/// 接受任意证书的校验器：不检查证书链，也不比对主机名
+///
+/// 仅用于`trust_server_certificate=true`，或者`Require`/`Prefer`在没有提供CA
+/// 证书时的降级路径——这两种场景下链路仍然是加密的，只是放弃了对证书真实性
+/// 的校验，行为等价于`psql sslmode=require`不带`sslrootcert`时的语义。
+struct AcceptAnyVerifier;
+
+impl ServerCertVerifier for AcceptAnyVerifier {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> std::result::Result<ServerCertVerified, RustlsError> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// 基于CA证书包做证书链校验；`verify_hostname=false`对应`SslMode::VerifyCa`，
+/// 只确认证书由受信CA签发，不比对`ServerName`；`true`对应`SslMode::VerifyFull`，
+/// 在此之上再校验主机名，直接复用rustls内置的`WebPkiVerifier`
+struct CaBundleVerifier {
+    inner: WebPkiVerifier,
+    verify_hostname: bool,
+}
+
+impl CaBundleVerifier {
+    fn load(ca_bundle_path: &str, verify_hostname: bool) -> Result<Self> {
+        let file = File::open(ca_bundle_path).map_err(|e| {
+            ConfigError::ParseError(format!("读取CA证书 {} 失败: {}", ca_bundle_path, e))
+        })?;
+        let mut reader = BufReader::new(file);
+        let der_certs = rustls_pemfile::certs(&mut reader)
+            .map_err(|e| ConfigError::ParseError(format!("解析CA证书 {} 失败: {}", ca_bundle_path, e)))?;
+
+        let mut roots = RootCertStore::empty();
+        for der in der_certs {
+            roots
+                .add(&Certificate(der))
+                .map_err(|e| ConfigError::ParseError(format!("加载CA证书 {} 失败: {}", ca_bundle_path, e)))?;
+        }
+
+        Ok(Self {
+            inner: WebPkiVerifier::new(roots, None),
+            verify_hostname,
+        })
+    }
+}
+
+impl ServerCertVerifier for CaBundleVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> std::result::Result<ServerCertVerified, RustlsError> {
+        self.inner
+            .verify_server_cert(end_entity, intermediates, server_name, scts, ocsp_response, now)
+            .or_else(|err| {
+                // VerifyCa不要求主机名匹配：链校验通过但仅因为主机名不符而失败时
+                // 视为可接受；真正的证书/签名/吊销等错误仍然原样返回
+                if !self.verify_hostname
+                    && matches!(
+                        err,
+                        RustlsError::InvalidCertificate(CertificateError::NotValidForName)
+                    )
+                {
+                    Ok(ServerCertVerified::assertion())
+                } else {
+                    Err(err)
+                }
+            })
+    }
+}
@@ -16,6 +16,16 @@ pub struct DatabaseConfig {
     pub ssl_config: Option<SslConfig>,
     pub timeout_config: TimeoutConfig,
     pub application_name: Option<String>,
+    /// 是否允许加载数据库扩展（目前仅SQLite后端的`load_extension`使用，默认关闭）
+    #[serde(default)]
+    pub allow_load_extension: bool,
+    /// 每连接预编译语句缓存的容量上限（目前仅SQLite后端使用）
+    #[serde(default = "default_prepared_cache_size")]
+    pub prepared_cache_size: usize,
+}
+
+fn default_prepared_cache_size() -> usize {
+    256
 }
 
 /// 支持的数据库类型
@@ -67,11 +77,15 @@ pub struct SslConfig {
     pub key_path: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SslMode {
     Disable,
     Require,
     Prefer,
+    /// 要求加密并校验证书链由`certificate_path`指定的CA签发，但不比对主机名
+    VerifyCa,
+    /// 要求加密并校验证书链与主机名，等价于标准TLS客户端的默认信任策略
+    VerifyFull,
 }
 
 /// 超时配置
@@ -104,6 +118,19 @@ pub struct PoolStatus {
     pub is_healthy: bool,
     pub last_error: Option<String>,
     pub uptime: Duration,
+    /// 熔断器当前状态（由 `HealthMonitor` 维护，后端自身构造时默认 `Closed`）
+    pub circuit_state: CircuitState,
+    /// 连续探测/查询失败次数（由 `HealthMonitor` 维护，后端自身构造时默认 0）
+    pub consecutive_failures: u32,
+}
+
+/// 熔断器状态机：Closed（正常）→ Open（连续失败达到阈值，直接拒绝）
+/// → HalfOpen（冷却结束后的单次试探）→ Closed 或 Open
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CircuitState {
+    Closed,
+    Open,
+    HalfOpen,
 }
 
 /// 连接池指标
@@ -113,11 +140,15 @@ pub struct PoolMetrics {
     pub queries_per_second: f64,
     pub connection_utilization: f64,
     pub avg_query_time: Duration,
+    pub p50_query_time: Duration,
     pub p99_query_time: Duration,
+    pub p999_query_time: Duration,
     pub error_rate: f64,
     pub total_queries: u64,
     pub total_errors: u64,
     pub cache_hit_rate: f64,
+    /// 因瞬时连接错误触发自动重试的累计次数
+    pub total_retries: u64,
 }
 
 /// 数据库值类型
@@ -133,11 +164,28 @@ pub enum DatabaseValue {
     Bytes(Vec<u8>),
     DateTime(chrono::DateTime<chrono::Utc>),
     Uuid(Uuid),
+    /// 精确小数（`DECIMAL`/`NUMERIC`/`MONEY`），不经浮点数中转以保留精度
+    Decimal(rust_decimal::Decimal),
+    /// 不带时间部分的日期（`DATE`）
+    Date(chrono::NaiveDate),
+    /// 不带日期部分的时间（`TIME`）
+    Time(chrono::NaiveTime),
+    /// 带偏移量的时间戳（`DATETIMEOFFSET`/`TIMESTAMPTZ`），区别于`DateTime`固定UTC的假设
+    DateTimeTz(chrono::DateTime<chrono::FixedOffset>),
 }
 
 /// 查询参数
 pub type QueryParams = HashMap<String, DatabaseValue>;
 
+/// `DatabasePool::query_with_params`结果集的解码方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ResultFormat {
+    /// 按驱动的二进制线缆表示精确解码数值/时间戳列（各后端已有的列类型分派路径）
+    Binary,
+    /// 不管原始列类型，统一物化为字符串；用于兼容性兜底或排查二进制解码分歧
+    Text,
+}
+
 /// 批处理操作
 #[derive(Debug, Clone)]
 pub struct BatchOperation {
@@ -153,6 +201,32 @@ pub struct BatchResult {
     pub error: Option<String>,
 }
 
+/// `describe()`返回的单列结构信息：只含列名与推断类型，不触发任何行的物化
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnSchema {
+    pub name: String,
+    pub data_type: polars::datatypes::DataType,
+}
+
+/// `DatabaseFactory::introspect_schema`待抽样的一张表
+///
+/// `pk_column`用于构造`DatabasePool::sample_table`的确定性排序表达式，需要是
+/// 数值类型（能`CAST AS BIGINT`）且取值唯一，通常就是表的主键列。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TableSampleSpec {
+    pub table: String,
+    pub pk_column: String,
+}
+
+/// `DataFrame`写回表时对目标表的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WriteMode {
+    /// 直接追加到现有数据之后
+    Append,
+    /// 写入前先`TRUNCATE TABLE`清空目标表
+    Truncate,
+}
+
 /// 部署模式
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum DeploymentMode {
@@ -174,6 +248,13 @@ pub enum FailoverStrategy {
         pools: Vec<String>,
         algorithm: LoadBalanceAlgorithm,
     },
+    /// 读写分离：写操作固定路由到 `primary`，读操作在健康的 `replicas` 间按
+    /// `algorithm` 分流，全部副本不健康时回退到 `primary`
+    ReadWriteSplit {
+        primary: String,
+        replicas: Vec<String>,
+        algorithm: LoadBalanceAlgorithm,
+    },
 }
 
 /// 负载均衡算法
@@ -1,11 +1,18 @@
 use crate::core::error::{DbPoolError, Result};
-use crate::core::types::{DatabaseConfig, DatabaseType};
+use crate::core::types::{DatabaseConfig, DatabaseType, TableSampleSpec};
 use crate::databases::traits::DatabasePool;
+use crate::utils::dataframe::DataFrameConverter;
 use std::sync::Arc;
 
-#[cfg(feature = "mssql")]
+#[cfg(feature = "mssql-native")]
 use crate::databases::mssql::MSSQLPool;
 
+#[cfg(feature = "postgres-native")]
+use crate::databases::postgresql::PostgreSQLPool;
+
+#[cfg(feature = "sqlite-native")]
+use crate::databases::sqlite::SQLitePool;
+
 /// 数据库工厂
 pub struct DatabaseFactory;
 
@@ -13,28 +20,38 @@ impl DatabaseFactory {
     /// 创建数据库连接池
     pub async fn create_pool(config: &DatabaseConfig) -> Result<Arc<dyn DatabasePool>> {
         match config.db_type {
-            #[cfg(feature = "mssql")]
+            #[cfg(feature = "mssql-native")]
             DatabaseType::MSSQL => {
                 let pool = MSSQLPool::new(config).await?;
                 Ok(Arc::new(pool))
             }
 
-            #[cfg(feature = "postgresql")]
+            #[cfg(not(feature = "mssql-native"))]
+            DatabaseType::MSSQL => Err(DbPoolError::Runtime(
+                "MSSQL支持未编译进当前构建：请在Cargo.toml中启用`mssql-native` feature后重新编译".to_string(),
+            )),
+
+            #[cfg(feature = "postgres-native")]
             DatabaseType::PostgreSQL => {
-                // TODO: 实现PostgreSQL支持
-                Err(DbPoolError::Runtime("PostgreSQL支持尚未实现".to_string()))
+                let pool = PostgreSQLPool::new(config).await?;
+                Ok(Arc::new(pool))
             }
 
-            #[cfg(feature = "redis")]
+            #[cfg(not(feature = "postgres-native"))]
+            DatabaseType::PostgreSQL => Err(DbPoolError::Runtime(
+                "PostgreSQL支持未编译进当前构建：请在Cargo.toml中启用`postgres-native` feature后重新编译".to_string(),
+            )),
+
+            #[cfg(feature = "redis-native")]
             DatabaseType::Redis => {
                 // TODO: 实现Redis支持
                 Err(DbPoolError::Runtime("Redis支持尚未实现".to_string()))
             }
 
-            #[cfg(feature = "sqlite")]
+            #[cfg(feature = "sqlite-native")]
             DatabaseType::SQLite => {
-                // TODO: 实现SQLite支持
-                Err(DbPoolError::Runtime("SQLite支持尚未实现".to_string()))
+                let pool = SQLitePool::new(config).await?;
+                Ok(Arc::new(pool))
             }
 
             DatabaseType::InfluxDB => {
@@ -53,27 +70,35 @@ impl DatabaseFactory {
     /// 验证数据库配置
     pub fn validate_config(config: &DatabaseConfig) -> Result<()> {
         match config.db_type {
-            #[cfg(feature = "mssql")]
+            #[cfg(feature = "mssql-native")]
             DatabaseType::MSSQL => {
                 MSSQLPool::validate_config(config)
             }
 
-            #[cfg(feature = "postgresql")]
+            #[cfg(not(feature = "mssql-native"))]
+            DatabaseType::MSSQL => Err(DbPoolError::Runtime(
+                "MSSQL支持未编译进当前构建：请在Cargo.toml中启用`mssql-native` feature后重新编译".to_string(),
+            )),
+
+            #[cfg(feature = "postgres-native")]
             DatabaseType::PostgreSQL => {
-                // TODO: 实现PostgreSQL配置验证
-                Ok(())
+                PostgreSQLPool::validate_config(config)
             }
 
-            #[cfg(feature = "redis")]
+            #[cfg(not(feature = "postgres-native"))]
+            DatabaseType::PostgreSQL => Err(DbPoolError::Runtime(
+                "PostgreSQL支持未编译进当前构建：请在Cargo.toml中启用`postgres-native` feature后重新编译".to_string(),
+            )),
+
+            #[cfg(feature = "redis-native")]
             DatabaseType::Redis => {
                 // TODO: 实现Redis配置验证
                 Ok(())
             }
 
-            #[cfg(feature = "sqlite")]
+            #[cfg(feature = "sqlite-native")]
             DatabaseType::SQLite => {
-                // TODO: 实现SQLite配置验证
-                Ok(())
+                SQLitePool::validate_config(config)
             }
 
             DatabaseType::InfluxDB => {
@@ -93,18 +118,65 @@ impl DatabaseFactory {
     pub fn supported_databases() -> Vec<DatabaseType> {
         let mut supported = Vec::new();
 
-        #[cfg(feature = "mssql")]
+        #[cfg(feature = "mssql-native")]
         supported.push(DatabaseType::MSSQL);
 
-        #[cfg(feature = "postgresql")]
+        #[cfg(feature = "postgres-native")]
         supported.push(DatabaseType::PostgreSQL);
 
-        #[cfg(feature = "redis")]
+        #[cfg(feature = "redis-native")]
         supported.push(DatabaseType::Redis);
 
-        #[cfg(feature = "sqlite")]
+        #[cfg(feature = "sqlite-native")]
         supported.push(DatabaseType::SQLite);
 
         supported
     }
+
+    /// 对`tables`逐一做确定性抽样（见`DatabasePool::sample_table`），在样本上跑
+    /// 整列类型推断与基础统计，汇总成一份按表名索引的schema自省JSON文档
+    ///
+    /// 产出的`dtypes`/`nullable`/`min_max`都只是对`sample_size`行样本的观测，
+    /// 不是整表扫描的结果；`sample_size`越小，越可能漏掉表中实际存在但样本未
+    /// 覆盖到的取值（如某一列的真实`min`/`max`、或某个在样本外才出现的dtype）。
+    pub async fn introspect_schema(
+        config: &DatabaseConfig,
+        tables: &[TableSampleSpec],
+        sample_size: usize,
+        seed: i64,
+    ) -> Result<serde_json::Value> {
+        let pool = Self::create_pool(config).await?;
+        let mut table_schemas = serde_json::Map::new();
+
+        for spec in tables {
+            let sample = pool
+                .sample_table(&spec.table, &spec.pk_column, sample_size, seed)
+                .await?;
+            let stats = DataFrameConverter::get_stats(&sample)?;
+
+            let columns: Vec<serde_json::Value> = sample
+                .get_column_names()
+                .into_iter()
+                .filter_map(|name| sample.column(name).ok().map(|series| (name, series)))
+                .map(|(name, series)| {
+                    serde_json::json!({
+                        "name": name,
+                        "dtype": format!("{:?}", series.dtype()),
+                        "nullable": series.null_count() > 0,
+                    })
+                })
+                .collect();
+
+            table_schemas.insert(
+                spec.table.clone(),
+                serde_json::json!({
+                    "columns": columns,
+                    "stats": stats,
+                    "sampled_rows": sample.height(),
+                }),
+            );
+        }
+
+        Ok(serde_json::Value::Object(table_schemas))
+    }
 }
\ No newline at end of file
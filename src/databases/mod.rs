@@ -1,14 +1,14 @@
 pub mod factory;
 pub mod traits;
 
-#[cfg(feature = "mssql")]
+#[cfg(feature = "mssql-native")]
 pub mod mssql;
 
-#[cfg(feature = "postgresql")]
+#[cfg(feature = "postgres-native")]
 pub mod postgresql;
 
-#[cfg(feature = "redis")]
+#[cfg(feature = "redis-native")]
 pub mod redis;
 
-#[cfg(feature = "sqlite")]
+#[cfg(feature = "sqlite-native")]
 pub mod sqlite;
\ No newline at end of file
@@ -1,8 +1,11 @@
-use crate::core::error::{QueryError, Result};
+use crate::core::error::{ConversionError, QueryError, Result};
 use crate::core::types::{QueryParams, DatabaseValue};
 use crate::databases::traits::{DatabaseConnection, DatabaseRow};
-use crate::databases::mssql::types::MSSQLRow;
-use tiberius::{Client, Row, QueryItem};
+use crate::databases::mssql::error::classify_query_error;
+use crate::databases::mssql::types::{MSSQLRow, MSSQLTypeConverter};
+use polars::frame::DataFrame;
+use std::collections::HashMap;
+use tiberius::{Client, Row, QueryItem, TokenRow};
 use tokio::net::TcpStream;
 use tokio_util::compat::Compat;
 use futures::TryStreamExt;
@@ -34,6 +37,90 @@ impl MSSQLConnection {
         }
     }
 
+    /// 只读取一条查询的结果集列元数据（名称+TDS类型），不物化任何行
+    ///
+    /// 服务器仍会在元数据之后继续推送行，这里照常耗尽流、直接丢弃行数据，
+    /// 以保证连接在归还连接池前处于干净状态。
+    pub async fn describe_columns(&mut self, sql: &str) -> Result<Vec<(String, tiberius::ColumnType)>> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let mut query = client
+            .query(sql, &[])
+            .await
+            .map_err(classify_query_error)?;
+
+        let mut columns = Vec::new();
+
+        while let Some(item) = query.try_next().await
+            .map_err(classify_query_error)?
+        {
+            if let QueryItem::Metadata(meta) = item {
+                if columns.is_empty() {
+                    columns = meta
+                        .columns()
+                        .iter()
+                        .map(|c| (c.name().to_string(), c.column_type()))
+                        .collect();
+                }
+            }
+        }
+
+        Ok(columns)
+    }
+
+    /// 以TDS批量插入（bulk insert）方式将整张DataFrame写入目标表
+    ///
+    /// 按`chunk_size`行切分为多个批次，每个批次各自开启一次`bulk_insert`请求，
+    /// 避免大DataFrame整体常驻在单次批次的内存中；返回实际写入的行数。
+    pub async fn bulk_insert_dataframe(
+        &mut self,
+        table: &str,
+        df: &DataFrame,
+        chunk_size: usize,
+    ) -> Result<u64> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let column_names = df.get_column_names();
+        let height = df.height();
+        let mut written: u64 = 0;
+        let mut offset = 0usize;
+
+        while offset < height {
+            let end = (offset + chunk_size.max(1)).min(height);
+
+            let mut req = client
+                .bulk_insert(table)
+                .await
+                .map_err(classify_query_error)?;
+
+            for row_idx in offset..end {
+                let mut token_row = TokenRow::new();
+                for name in &column_names {
+                    let series = df
+                        .column(name)
+                        .map_err(|e| ConversionError::TypeConversion(e.to_string()))?;
+                    let any_value = series
+                        .get(row_idx)
+                        .map_err(|e| ConversionError::TypeConversion(e.to_string()))?;
+                    token_row.push(MSSQLTypeConverter::any_value_to_column_data(any_value));
+                }
+
+                req.send(token_row)
+                    .await
+                    .map_err(classify_query_error)?;
+                written += 1;
+            }
+
+            req.finalize()
+                .await
+                .map_err(classify_query_error)?;
+
+            offset = end;
+        }
+
+        Ok(written)
+    }
+
     /// 将DatabaseValue转换为tiberius支持的类型
     /// 返回一个装箱的trait对象，避免临时值问题
     fn database_value_to_tiberius_param(value: DatabaseValue) -> Box<dyn tiberius::ToSql + Send + Sync> {
@@ -48,8 +135,93 @@ impl MSSQLConnection {
             DatabaseValue::Bytes(b) => Box::new(b),
             DatabaseValue::DateTime(dt) => Box::new(dt.timestamp()),
             DatabaseValue::Uuid(u) => Box::new(u),
+            DatabaseValue::Decimal(d) => Box::new(d),
+            DatabaseValue::Date(d) => Box::new(d),
+            DatabaseValue::Time(t) => Box::new(t),
+            DatabaseValue::DateTimeTz(dt) => Box::new(dt),
         }
     }
+
+    /// 把SQL中形如`:name`的具名占位符替换为TDS的位置占位符`@P1`..`@Pn`，
+    /// 同一个名字在同一条SQL里重复出现时复用同一个`@Pn`
+    ///
+    /// 不会识别出现在字符串字面量内部的`:name`，调用方应避免在SQL字面量
+    /// 文本中拼接冒号加标识符的内容。
+    fn bind_named_params(
+        sql: &str,
+        named: &[(String, DatabaseValue)],
+    ) -> Result<(String, Vec<Box<dyn tiberius::ToSql + Send + Sync>>)> {
+        let chars: Vec<char> = sql.chars().collect();
+        let mut output = String::with_capacity(sql.len());
+        let mut values: Vec<Box<dyn tiberius::ToSql + Send + Sync>> = Vec::new();
+        let mut placeholder_index: HashMap<String, usize> = HashMap::new();
+
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] == ':' && i + 1 < chars.len() && (chars[i + 1].is_alphabetic() || chars[i + 1] == '_') {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let name: String = chars[start..end].iter().collect();
+
+                let idx = if let Some(&idx) = placeholder_index.get(&name) {
+                    idx
+                } else {
+                    let (_, value) = named
+                        .iter()
+                        .find(|(n, _)| n == &name)
+                        .ok_or_else(|| QueryError::ParameterBinding(format!("缺少参数: {}", name)))?;
+                    values.push(Self::database_value_to_tiberius_param(value.clone()));
+                    let idx = values.len();
+                    placeholder_index.insert(name, idx);
+                    idx
+                };
+
+                output.push_str("@P");
+                output.push_str(&idx.to_string());
+                i = end;
+            } else {
+                output.push(chars[i]);
+                i += 1;
+            }
+        }
+
+        Ok((output, values))
+    }
+
+    /// 按位置绑定参数执行查询：`sql`使用TDS的`@P1`..`@Pn`占位符，`params`按顺序绑定
+    ///
+    /// tiberius不像tokio-postgres那样提供“不执行即可拿到占位符类型”的探测，
+    /// 因此这里不做参数类型校验，类型不匹配会在驱动执行时报错
+    pub async fn query_positional(&mut self, sql: &str, params: &[DatabaseValue]) -> Result<Vec<MSSQLRow>> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let values: Vec<Box<dyn tiberius::ToSql + Send + Sync>> = params
+            .iter()
+            .cloned()
+            .map(Self::database_value_to_tiberius_param)
+            .collect();
+        let value_refs: Vec<&dyn tiberius::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+
+        let mut query = client
+            .query(sql, &value_refs[..])
+            .await
+            .map_err(classify_query_error)?;
+
+        let mut rows = Vec::new();
+
+        while let Some(item) = query.try_next().await
+            .map_err(classify_query_error)?
+        {
+            if let QueryItem::Row(row) = item {
+                rows.push(MSSQLRow::new(row));
+            }
+        }
+
+        Ok(rows)
+    }
 }
 
 #[async_trait::async_trait]
@@ -57,17 +229,21 @@ impl DatabaseConnection for MSSQLConnection {
     type Row = MSSQLRow;
 
     async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<Vec<Self::Row>> {
+        let named = Self::convert_params(params);
+        let (bound_sql, values) = Self::bind_named_params(sql, &named)?;
+        let value_refs: Vec<&dyn tiberius::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+
         let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
-        
+
         let mut query = client
-            .query(sql, &[])
+            .query(&bound_sql, &value_refs[..])
             .await
-            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+            .map_err(classify_query_error)?;
 
         let mut rows = Vec::new();
 
         while let Some(item) = query.try_next().await
-            .map_err(|e| QueryError::ResultProcessing(e.to_string()))? 
+            .map_err(classify_query_error)?
         {
             match item {
                 QueryItem::Row(row) => {
@@ -82,13 +258,64 @@ impl DatabaseConnection for MSSQLConnection {
         Ok(rows)
     }
 
+    async fn query_stream(
+        &mut self,
+        sql: &str,
+        params: Option<QueryParams>,
+        buffer_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<Self::Row>>> {
+        let mut client = self.client.take().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let named = Self::convert_params(params);
+        let (bound_sql, values) = Self::bind_named_params(sql, &named)?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(buffer_size.max(1));
+
+        // 流耗尽（或消费者丢弃`rx`导致发送失败）后`client`随任务一起被丢弃，
+        // 不会还给`self.client`：`self`之后不能再发起新的查询，需调用方重新从
+        // 连接池取一个连接
+        tokio::spawn(async move {
+            let value_refs: Vec<&dyn tiberius::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+
+            let mut query = match client.query(&bound_sql, &value_refs[..]).await {
+                Ok(query) => query,
+                Err(e) => {
+                    let _ = tx.send(Err(classify_query_error(e))).await;
+                    return;
+                }
+            };
+
+            loop {
+                match query.try_next().await {
+                    Ok(Some(QueryItem::Row(row))) => {
+                        if tx.send(Ok(MSSQLRow::new(row))).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(Some(QueryItem::Metadata(_))) => continue,
+                    Ok(None) => return,
+                    Err(e) => {
+                        let _ = tx.send(Err(classify_query_error(e))).await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
     async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        let named = Self::convert_params(params);
+        let (bound_sql, values) = Self::bind_named_params(sql, &named)?;
+        let value_refs: Vec<&dyn tiberius::ToSql> = values.iter().map(|v| v.as_ref()).collect();
+
         let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
-        
+
         let result = client
-            .execute(sql, &[])
+            .execute(&bound_sql, &value_refs[..])
             .await
-            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+            .map_err(classify_query_error)?;
 
         Ok(result.rows_affected().len() as u64)
     }
@@ -99,11 +326,11 @@ impl DatabaseConnection for MSSQLConnection {
         }
 
         let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
-        
+
         client
             .simple_query("BEGIN TRANSACTION")
             .await
-            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+            .map_err(classify_query_error)?;
 
         self.in_transaction = true;
         Ok(())
@@ -115,11 +342,11 @@ impl DatabaseConnection for MSSQLConnection {
         }
 
         let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
-        
+
         client
             .simple_query("COMMIT TRANSACTION")
             .await
-            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+            .map_err(classify_query_error)?;
 
         self.in_transaction = false;
         Ok(())
@@ -131,11 +358,11 @@ impl DatabaseConnection for MSSQLConnection {
         }
 
         let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
-        
+
         client
             .simple_query("ROLLBACK TRANSACTION")
             .await
-            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+            .map_err(classify_query_error)?;
 
         self.in_transaction = false;
         Ok(())
@@ -0,0 +1,120 @@
+/// SQL Server返回的错误号/严重级别/状态，以及归类后的错误类别
+///
+/// 取代把tiberius的`TokenError`直接拍扁成字符串塞进`ConnectionError::ConnectionFailed`/
+/// `QueryError::ExecutionFailed`的做法，让调用方能按`category`匹配（如区分死锁与
+/// 唯一键冲突），而不必记住具体错误号。
+#[derive(Debug, Clone)]
+pub struct SqlServerError {
+    /// SQL Server错误号，如1205（死锁）、2627（唯一约束冲突）、18456（登录失败）
+    pub number: u32,
+    /// 严重级别（class）
+    pub severity: u8,
+    /// 错误状态（state）
+    pub state: u8,
+    pub message: String,
+    pub category: SqlServerErrorCategory,
+}
+
+impl std::fmt::Display for SqlServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "SQL Server错误{}（severity={}, state={}）: {}",
+            self.number, self.severity, self.state, self.message
+        )
+    }
+}
+
+impl std::error::Error for SqlServerError {}
+
+/// 按SQL Server错误号归类的错误类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlServerErrorCategory {
+    /// 1205：作为死锁牺牲品被终止，可安全重试同一事务
+    Deadlock,
+    /// 2627（唯一约束）/2601（唯一索引）
+    UniqueViolation,
+    /// 547：外键/CHECK约束冲突——SQL Server对两者共用同一个错误号，这里统一
+    /// 归入外键违反，`message`里仍带有约束名可供区分
+    ForeignKeyViolation,
+    /// 515：试图向`NOT NULL`列插入空值
+    NotNullViolation,
+    /// 18456：登录失败
+    AuthenticationFailed,
+    /// 229/230：权限不足
+    PermissionDenied,
+    /// 1222：锁请求超时
+    Timeout,
+    /// 未归类的其它错误号
+    Other,
+}
+
+impl SqlServerError {
+    /// 从tiberius错误中提取结构化信息；非`Error::Server`（如IO/协议错误）返回`None`
+    pub fn from_tiberius(err: &tiberius::error::Error) -> Option<Self> {
+        match err {
+            tiberius::error::Error::Server(token_error) => {
+                let number = token_error.code();
+                Some(Self {
+                    number,
+                    severity: token_error.class(),
+                    state: token_error.state(),
+                    message: token_error.message().to_string(),
+                    category: Self::classify(number),
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn classify(number: u32) -> SqlServerErrorCategory {
+        match number {
+            1205 => SqlServerErrorCategory::Deadlock,
+            2627 | 2601 => SqlServerErrorCategory::UniqueViolation,
+            547 => SqlServerErrorCategory::ForeignKeyViolation,
+            515 => SqlServerErrorCategory::NotNullViolation,
+            18456 => SqlServerErrorCategory::AuthenticationFailed,
+            229 | 230 => SqlServerErrorCategory::PermissionDenied,
+            1222 => SqlServerErrorCategory::Timeout,
+            _ => SqlServerErrorCategory::Other,
+        }
+    }
+
+    /// 映射到`core::sqlstate::SqlState`，让`DbPoolError::is_unique_violation`等
+    /// 跨后端的语义判断也能覆盖MSSQL，不必让调用方分别处理
+    /// `DbPoolError::SqlServer`和`DbPoolError::Database`两种变体
+    pub fn sqlstate(&self) -> crate::core::sqlstate::SqlState {
+        match self.category {
+            SqlServerErrorCategory::UniqueViolation => crate::core::sqlstate::SqlState::UniqueViolation,
+            SqlServerErrorCategory::ForeignKeyViolation => crate::core::sqlstate::SqlState::ForeignKeyViolation,
+            SqlServerErrorCategory::NotNullViolation => crate::core::sqlstate::SqlState::NotNullViolation,
+            SqlServerErrorCategory::Deadlock => crate::core::sqlstate::SqlState::DeadlockDetected,
+            SqlServerErrorCategory::Timeout => crate::core::sqlstate::SqlState::LockNotAvailable,
+            SqlServerErrorCategory::AuthenticationFailed => crate::core::sqlstate::SqlState::InvalidPassword,
+            SqlServerErrorCategory::PermissionDenied => crate::core::sqlstate::SqlState::InsufficientPrivilege,
+            SqlServerErrorCategory::Other => crate::core::sqlstate::SqlState::Other(self.number.to_string()),
+        }
+    }
+}
+
+/// 对查询/执行路径上的tiberius错误分类：能解析出SQL Server错误号时返回
+/// `DbPoolError::SqlServer`，否则退化为原先的`QueryError::ExecutionFailed`字符串兜底
+pub fn classify_query_error(err: tiberius::error::Error) -> crate::core::error::DbPoolError {
+    match SqlServerError::from_tiberius(&err) {
+        Some(sql_err) => crate::core::error::DbPoolError::SqlServer(sql_err),
+        None => crate::core::error::DbPoolError::Query(
+            crate::core::error::QueryError::ExecutionFailed(err.to_string()),
+        ),
+    }
+}
+
+/// 同`classify_query_error`，用于建连阶段（如18456登录失败），退化为
+/// `ConnectionError::ConnectionFailed`
+pub fn classify_connection_error(err: tiberius::error::Error) -> crate::core::error::DbPoolError {
+    match SqlServerError::from_tiberius(&err) {
+        Some(sql_err) => crate::core::error::DbPoolError::SqlServer(sql_err),
+        None => crate::core::error::DbPoolError::Connection(
+            crate::core::error::ConnectionError::ConnectionFailed(err.to_string()),
+        ),
+    }
+}
@@ -1,8 +1,10 @@
 pub mod config;
 pub mod connection;
+pub mod error;
 pub mod pool;
 pub mod types;
 
 pub use pool::MSSQLPool;
 pub use connection::MSSQLConnection;
+pub use error::{SqlServerError, SqlServerErrorCategory};
 pub use types::MSSQLRow;
\ No newline at end of file
@@ -1,8 +1,9 @@
 use crate::core::error::{DbPoolError, Result, ConnectionError};
-use crate::core::types::{QueryParams, DatabaseValue, BatchOperation, BatchResult, PoolStatus, DatabaseType};
-use crate::databases::traits::{DatabasePool, DatabaseConnection, DatabaseRow, TypeConverter};
+use crate::core::types::{QueryParams, DatabaseValue, BatchOperation, BatchResult, PoolStatus, DatabaseType, ResultFormat};
+use crate::databases::traits::{DatabasePool, DatabaseConnection, DatabaseRow, TransactionSession, TypeConverter, rows_to_text_dataframe};
 use crate::databases::mssql::connection::MSSQLConnection;
-use crate::databases::mssql::types::MSSQLRow;
+use crate::databases::mssql::types::{MSSQLRow, MSSQLTypeConverter};
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{RwLock, Semaphore};
@@ -11,12 +12,21 @@ use tokio::net::TcpStream;
 use tokio_util::compat::{TokioAsyncWriteCompatExt, Compat};
 use std::collections::VecDeque;
 
+/// 最近一次建连/健康探测的结果，供 `get_status` 汇报 `is_healthy`/`last_error`
+struct HealthState {
+    is_healthy: bool,
+    last_error: Option<String>,
+}
+
 /// MSSQL连接池
 pub struct MSSQLPool {
     config: crate::core::types::DatabaseConfig,
     connections: Arc<RwLock<VecDeque<MSSQLConnection>>>,
     semaphore: Arc<Semaphore>,
     total_connections: Arc<RwLock<u32>>,
+    /// 当前阻塞在`get_connection`的`semaphore.acquire()`里的任务数
+    waiting_acquires: Arc<AtomicU32>,
+    health: Arc<RwLock<HealthState>>,
     created_at: Instant,
 }
 
@@ -30,6 +40,11 @@ impl MSSQLPool {
             connections: Arc::new(RwLock::new(VecDeque::new())),
             semaphore: Arc::new(Semaphore::new(config.pool_config.max_connections as usize)),
             total_connections: Arc::new(RwLock::new(0)),
+            waiting_acquires: Arc::new(AtomicU32::new(0)),
+            health: Arc::new(RwLock::new(HealthState {
+                is_healthy: true,
+                last_error: None,
+            })),
             created_at: Instant::now(),
         };
 
@@ -53,6 +68,10 @@ impl MSSQLPool {
             return Err(DbPoolError::Runtime("MSSQL用户名不能为空".to_string()));
         }
 
+        if let Some(ssl_config) = &config.ssl_config {
+            crate::core::tls::resolve_policy(ssl_config)?;
+        }
+
         Ok(())
     }
 
@@ -110,6 +129,12 @@ impl MSSQLPool {
                 crate::core::types::SslMode::Prefer => {
                     tiberius_config.encryption(tiberius::EncryptionLevel::On);
                 }
+                crate::core::types::SslMode::VerifyCa | crate::core::types::SslMode::VerifyFull => {
+                    // tiberius的TLS校验要么信任系统根证书要么`trust_cert()`完全跳过，
+                    // 不暴露自定义`ServerCertVerifier`的接入点，因此这里只能保证
+                    // 加密强制开启；真正的CA校验留给下面的`resolve_policy`做组合校验
+                    tiberius_config.encryption(tiberius::EncryptionLevel::Required);
+                }
             }
             
             if ssl_config.trust_server_certificate {
@@ -131,20 +156,40 @@ impl MSSQLPool {
         .map_err(|e| ConnectionError::ConnectionFailed(e.to_string()))?;
 
         let client = Client::connect(tiberius_config, tcp.compat_write()).await
-            .map_err(|e| ConnectionError::ConnectionFailed(e.to_string()))?;
+            .map_err(crate::databases::mssql::error::classify_connection_error);
+
+        match client {
+            Ok(client) => {
+                self.record_health(true, None).await;
+                Ok(MSSQLConnection::new(client))
+            }
+            Err(e) => {
+                self.record_health(false, Some(e.to_string())).await;
+                Err(e)
+            }
+        }
+    }
 
-        Ok(MSSQLConnection::new(client))
+    /// 记录最近一次建连/健康探测的结果，供`get_status`汇报
+    async fn record_health(&self, is_healthy: bool, last_error: Option<String>) {
+        let mut health = self.health.write().await;
+        health.is_healthy = is_healthy;
+        health.last_error = last_error;
     }
 
     /// 获取连接
     async fn get_connection(&self) -> Result<MSSQLConnection> {
-        // 等待信号量
-        let _permit = tokio::time::timeout(
+        // 等待信号量期间计入waiting_connections，获取到permit（或超时/失败）后立即退出计数
+        self.waiting_acquires.fetch_add(1, Ordering::SeqCst);
+        let permit_result = tokio::time::timeout(
             self.config.pool_config.acquire_timeout,
             self.semaphore.acquire()
-        ).await
-        .map_err(|_| ConnectionError::AcquireTimeout)?
-        .map_err(|_| ConnectionError::PoolExhausted)?;
+        ).await;
+        self.waiting_acquires.fetch_sub(1, Ordering::SeqCst);
+
+        let _permit = permit_result
+            .map_err(|_| ConnectionError::AcquireTimeout)?
+            .map_err(|_| ConnectionError::PoolExhausted)?;
 
         // 尝试从池中获取连接
         {
@@ -206,6 +251,69 @@ impl DatabasePool for MSSQLPool {
         }
     }
 
+    async fn execute_query_rows(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+    ) -> Result<Vec<Box<dyn DatabaseRow>>> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query(sql, params).await;
+        self.return_connection(connection).await;
+
+        result.map(|rows| {
+            rows.into_iter()
+                .map(|row| Box::new(row) as Box<dyn DatabaseRow>)
+                .collect()
+        })
+    }
+
+    /// 真正的惰性游标实现：借`DatabaseConnection::query_stream`逐行拉取，每凑够
+    /// `batch_size`行就转换成一个`DataFrame`批次发出，不会先把整个结果集
+    /// 物化在内存里
+    async fn execute_query_stream(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+        batch_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<polars::frame::DataFrame>>> {
+        let batch_size = batch_size.max(1);
+        let mut connection = self.get_connection().await?;
+        let mut row_rx = connection.query_stream(sql, params, batch_size * 2).await?;
+        // `query_stream`已经取走了底层`client`，这里只是让`return_connection`
+        // 观察到`is_valid()==false`从而正确扣减`total_connections`，而不是把
+        // 一个已被取走client的连接放回可复用队列
+        self.return_connection(connection).await;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        tokio::spawn(async move {
+            let mut buffer = Vec::with_capacity(batch_size);
+            while let Some(row_result) = row_rx.recv().await {
+                match row_result {
+                    Ok(row) => {
+                        buffer.push(row);
+                        if buffer.len() >= batch_size {
+                            let chunk = std::mem::take(&mut buffer);
+                            let batch = MSSQLTypeConverter::rows_to_dataframe(chunk);
+                            if tx.send(batch).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+            }
+            if !buffer.is_empty() {
+                let batch = MSSQLTypeConverter::rows_to_dataframe(buffer);
+                let _ = tx.send(batch).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
     async fn execute_non_query(&self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
         let mut connection = self.get_connection().await?;
         let result = connection.execute(sql, params).await;
@@ -213,6 +321,40 @@ impl DatabasePool for MSSQLPool {
         result
     }
 
+    /// 按位置绑定参数执行查询：`sql`使用TDS的`@P1`..`@Pn`占位符
+    ///
+    /// `ResultFormat::Binary`复用`execute_query`已有的按`ColumnType`精确解码路径；
+    /// `ResultFormat::Text`不管原始列类型，统一把每个值物化为字符串
+    async fn query_with_params(
+        &self,
+        sql: &str,
+        params: &[DatabaseValue],
+        result_format: ResultFormat,
+    ) -> Result<polars::frame::DataFrame> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query_positional(sql, params).await;
+        self.return_connection(connection).await;
+        let rows = result?;
+
+        match result_format {
+            ResultFormat::Binary => MSSQLTypeConverter::rows_to_dataframe(rows),
+            ResultFormat::Text => rows_to_text_dataframe(rows),
+        }
+    }
+
+    /// T-SQL没有`LIMIT`子句，改用`TOP`；排序表达式与默认实现保持一致
+    async fn sample_table(&self, table: &str, pk_column: &str, n: usize, seed: i64) -> Result<polars::frame::DataFrame> {
+        crate::databases::traits::validate_sql_identifier(table)?;
+        crate::databases::traits::validate_sql_identifier(pk_column)?;
+        let sql = format!(
+            "SELECT TOP {n} * FROM {table} ORDER BY {order}",
+            n = n,
+            table = table,
+            order = crate::databases::traits::deterministic_sample_order(pk_column, seed)
+        );
+        self.execute_query(&sql, None).await
+    }
+
     async fn execute_batch(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>> {
         let mut connection = self.get_connection().await?;
         let mut results = Vec::new();
@@ -244,55 +386,130 @@ impl DatabasePool for MSSQLPool {
         Ok(results)
     }
 
+    /// 死锁（1205）是SQL Server选中某一方作为牺牲品回滚后的正常现象，整个事务
+    /// 从头重试通常就能成功，因此这里比非死锁错误多给几次重试机会
     async fn execute_transaction(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>> {
-        let mut connection = self.get_connection().await?;
-        let mut results = Vec::new();
+        const MAX_DEADLOCK_RETRIES: u32 = 3;
+
+        let mut attempt = 0u32;
+        loop {
+            let mut connection = self.get_connection().await?;
+            let mut results = Vec::new();
+
+            connection.begin_transaction().await?;
+
+            let mut failure: Option<DbPoolError> = None;
+
+            for operation in operations.clone() {
+                let start_time = Instant::now();
+                let result = connection.execute(&operation.sql, operation.params).await;
+                let execution_time = start_time.elapsed();
+
+                match result {
+                    Ok(affected_rows) => {
+                        results.push(BatchResult {
+                            affected_rows,
+                            execution_time,
+                            error: None,
+                        });
+                    }
+                    Err(e) => {
+                        results.push(BatchResult {
+                            affected_rows: 0,
+                            execution_time,
+                            error: Some(e.to_string()),
+                        });
+                        failure = Some(e);
+                        break;
+                    }
+                }
+            }
 
-        // 开始事务
-        connection.begin_transaction().await?;
+            if let Some(e) = failure {
+                connection.rollback_transaction().await?;
+                self.return_connection(connection).await;
 
-        let mut transaction_failed = false;
+                let is_deadlock = matches!(
+                    &e,
+                    DbPoolError::SqlServer(sql_err)
+                        if sql_err.category == crate::databases::mssql::error::SqlServerErrorCategory::Deadlock
+                );
 
-        for operation in operations {
-            let start_time = Instant::now();
-            let result = connection.execute(&operation.sql, operation.params).await;
-            let execution_time = start_time.elapsed();
-
-            match result {
-                Ok(affected_rows) => {
-                    results.push(BatchResult {
-                        affected_rows,
-                        execution_time,
-                        error: None,
-                    });
-                }
-                Err(e) => {
-                    results.push(BatchResult {
-                        affected_rows: 0,
-                        execution_time,
-                        error: Some(e.to_string()),
-                    });
-                    transaction_failed = true;
-                    break;
+                if is_deadlock && attempt + 1 < MAX_DEADLOCK_RETRIES {
+                    attempt += 1;
+                    continue;
                 }
+
+                return Err(e);
             }
-        }
 
-        // 提交或回滚事务
-        if transaction_failed {
-            connection.rollback_transaction().await?;
-        } else {
             connection.commit_transaction().await?;
+            self.return_connection(connection).await;
+            return Ok(results);
+        }
+    }
+
+    async fn begin_session(&self) -> Result<Box<dyn TransactionSession>> {
+        let mut connection = self.get_connection().await?;
+        connection.begin_transaction().await?;
+
+        Ok(Box::new(MSSQLTransactionSession {
+            connection: Some(connection),
+            total_connections: Arc::clone(&self.total_connections),
+            savepoint_counter: 0,
+        }))
+    }
+
+    /// 将DataFrame批量写入目标表；`WriteMode::Truncate`先清空目标表再批量插入
+    async fn write_dataframe(
+        &self,
+        table: &str,
+        df: &polars::frame::DataFrame,
+        mode: crate::core::types::WriteMode,
+    ) -> Result<u64> {
+        /// 每批TDS批量插入携带的最大行数，避免整张DataFrame挤在单次批次里
+        const BULK_INSERT_CHUNK_SIZE: usize = 10_000;
+
+        crate::databases::traits::validate_sql_identifier(table)?;
+
+        let mut connection = self.get_connection().await?;
+
+        if mode == crate::core::types::WriteMode::Truncate {
+            if let Err(e) = connection.execute(&format!("TRUNCATE TABLE {}", table), None).await {
+                self.return_connection(connection).await;
+                return Err(e);
+            }
         }
 
+        let result = connection
+            .bulk_insert_dataframe(table, df, BULK_INSERT_CHUNK_SIZE)
+            .await;
         self.return_connection(connection).await;
-        Ok(results)
+        result
+    }
+
+    /// 获取一条查询结果集的列名与推断类型，不拉取任何行
+    async fn describe(&self, sql: &str) -> Result<Vec<crate::core::types::ColumnSchema>> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.describe_columns(sql).await;
+        self.return_connection(connection).await;
+
+        result.map(|columns| {
+            columns
+                .into_iter()
+                .map(|(name, column_type)| crate::core::types::ColumnSchema {
+                    name,
+                    data_type: crate::databases::mssql::types::column_type_to_dtype(column_type),
+                })
+                .collect()
+        })
     }
 
     async fn get_status(&self) -> Result<PoolStatus> {
         let connections = self.connections.read().await;
         let total = self.total_connections.read().await;
         let active = *total - connections.len() as u32;
+        let health = self.health.read().await;
 
         Ok(PoolStatus {
             pool_id: "mssql_pool".to_string(),
@@ -300,18 +517,26 @@ impl DatabasePool for MSSQLPool {
             total_connections: *total,
             active_connections: active,
             idle_connections: connections.len() as u32,
-            waiting_connections: 0, // 简化实现
-            is_healthy: true,
-            last_error: None,
+            waiting_connections: self.waiting_acquires.load(Ordering::SeqCst),
+            is_healthy: health.is_healthy,
+            last_error: health.last_error.clone(),
             uptime: self.created_at.elapsed(),
+            circuit_state: crate::core::types::CircuitState::Closed, // 由HealthMonitor覆盖
+            consecutive_failures: 0,
         })
     }
 
     async fn health_check(&self) -> Result<bool> {
         // 尝试执行简单查询
         match self.execute_query("SELECT 1", None).await {
-            Ok(_) => Ok(true),
-            Err(_) => Ok(false),
+            Ok(_) => {
+                self.record_health(true, None).await;
+                Ok(true)
+            }
+            Err(e) => {
+                self.record_health(false, Some(e.to_string())).await;
+                Ok(false)
+            }
         }
     }
 
@@ -331,4 +556,76 @@ impl DatabasePool for MSSQLPool {
 
         Ok(())
     }
+}
+
+/// 从 `MSSQLPool` 租用的事务会话
+///
+/// 简化实现：结束（提交/回滚）后直接关闭底层连接而不归还到池，避免为
+/// 租用期引入与 `MSSQLPool` 的共享所有权生命周期管理；连接计数会相应递减。
+struct MSSQLTransactionSession {
+    connection: Option<MSSQLConnection>,
+    total_connections: Arc<RwLock<u32>>,
+    savepoint_counter: u32,
+}
+
+impl MSSQLTransactionSession {
+    fn connection_mut(&mut self) -> Result<&mut MSSQLConnection> {
+        self.connection
+            .as_mut()
+            .ok_or_else(|| DbPoolError::Runtime("事务会话已结束".to_string()))
+    }
+
+    async fn finish(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            let _ = connection.close().await;
+            let mut total = self.total_connections.write().await;
+            if *total > 0 {
+                *total -= 1;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionSession for MSSQLTransactionSession {
+    async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<polars::frame::DataFrame> {
+        let rows = self.connection_mut()?.query(sql, params).await?;
+        crate::databases::mssql::types::MSSQLTypeConverter::rows_to_dataframe(rows)
+    }
+
+    async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        self.connection_mut()?.execute(sql, params).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.connection_mut()?.commit_transaction().await?;
+        self.finish().await;
+        Ok(())
+    }
+
+    async fn rollback(&mut self) -> Result<()> {
+        self.connection_mut()?.rollback_transaction().await?;
+        self.finish().await;
+        Ok(())
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoint_counter += 1;
+        self.connection_mut()?
+            .execute(&format!("SAVE TRANSACTION {}", name), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self, _name: &str) -> Result<()> {
+        // SQL Server没有显式的"释放保存点"语句，保存点随事务提交/回滚自动失效
+        Ok(())
+    }
+
+    async fn rollback_to(&mut self, name: &str) -> Result<()> {
+        self.connection_mut()?
+            .execute(&format!("ROLLBACK TRANSACTION {}", name), None)
+            .await?;
+        Ok(())
+    }
 }
\ No newline at end of file
@@ -3,13 +3,15 @@ use crate::core::types::DatabaseValue;
 use crate::databases::traits::{DatabaseRow, TypeConverter};
 use polars::prelude::*;
 use std::collections::HashMap;
-use tiberius::{Row, ColumnData};
+use tiberius::{ColumnData, ColumnType, Row};
 use uuid::Uuid;
 
 /// MSSQL行数据
 pub struct MSSQLRow {
     row: Row,
     column_names: Vec<String>,
+    /// 每列声明的TDS类型，随行一起缓存，驱动`get_value`选择唯一匹配的`try_get`
+    column_types: Vec<ColumnType>,
 }
 
 impl MSSQLRow {
@@ -19,8 +21,9 @@ impl MSSQLRow {
             .iter()
             .map(|col| col.name().to_string())
             .collect();
+        let column_types = row.columns().iter().map(|col| col.column_type()).collect();
 
-        Self { row, column_names }
+        Self { row, column_names, column_types }
     }
 }
 
@@ -33,49 +36,97 @@ impl DatabaseRow for MSSQLRow {
         self.column_names.clone()
     }
 
+    /// 按该列声明的TDS类型精确选择一种`try_get`，而非依次尝试各Rust类型猜测
+    ///
+    /// 例如`NUMERIC`恰好能解析成字符串也不会被误判为`String`；`try_get`对
+    /// `None`（SQL NULL）与类型不匹配返回的`Err`都统一折叠为`None`。
     fn get_value(&self, index: usize) -> Option<DatabaseValue> {
-        if index >= self.row.len() {
-            return None;
-        }
+        let column_type = *self.column_types.get(index)?;
 
-        // 使用try_get来处理可能的错误
-        // 由于我们不知道确切的类型，我们需要尝试不同的类型
-        
-        // 尝试字符串
-        if let Ok(Some(val)) = self.row.try_get::<&str, _>(index) {
-            return Some(DatabaseValue::String(val.to_string()));
-        }
-        
-        // 尝试整数
-        if let Ok(Some(val)) = self.row.try_get::<i32, _>(index) {
-            return Some(DatabaseValue::I32(val));
-        }
-        
-        if let Ok(Some(val)) = self.row.try_get::<i64, _>(index) {
-            return Some(DatabaseValue::I64(val));
-        }
-        
-        // 尝试浮点数
-        if let Ok(Some(val)) = self.row.try_get::<f32, _>(index) {
-            return Some(DatabaseValue::F32(val));
-        }
-        
-        if let Ok(Some(val)) = self.row.try_get::<f64, _>(index) {
-            return Some(DatabaseValue::F64(val));
-        }
-        
-        // 尝试布尔值
-        if let Ok(Some(val)) = self.row.try_get::<bool, _>(index) {
-            return Some(DatabaseValue::Bool(val));
-        }
-        
-        // 尝试UUID
-        if let Ok(Some(val)) = self.row.try_get::<uuid::Uuid, _>(index) {
-            return Some(DatabaseValue::Uuid(val));
+        match column_type {
+            ColumnType::Bit | ColumnType::Bitn => {
+                self.row.try_get::<bool, _>(index).ok().flatten().map(DatabaseValue::Bool)
+            }
+            ColumnType::Int1 => self
+                .row
+                .try_get::<u8, _>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::I32(v as i32)),
+            ColumnType::Int2 => self
+                .row
+                .try_get::<i16, _>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::I32(v as i32)),
+            ColumnType::Int4 => self.row.try_get::<i32, _>(index).ok().flatten().map(DatabaseValue::I32),
+            ColumnType::Int8 | ColumnType::Intn => {
+                self.row.try_get::<i64, _>(index).ok().flatten().map(DatabaseValue::I64)
+            }
+            ColumnType::Float4 => self.row.try_get::<f32, _>(index).ok().flatten().map(DatabaseValue::F32),
+            ColumnType::Float8 | ColumnType::Floatn => {
+                self.row.try_get::<f64, _>(index).ok().flatten().map(DatabaseValue::F64)
+            }
+            // `MONEY`/`DECIMAL`/`NUMERIC`经`rust_decimal::Decimal`精确取值，不经f64中转丢精度
+            ColumnType::Money | ColumnType::Money4 | ColumnType::Decimaln | ColumnType::Numericn => self
+                .row
+                .try_get::<rust_decimal::Decimal, _>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Decimal),
+            ColumnType::Guid => self.row.try_get::<Uuid, _>(index).ok().flatten().map(DatabaseValue::Uuid),
+            ColumnType::BigVarChar
+            | ColumnType::BigChar
+            | ColumnType::NVarchar
+            | ColumnType::NChar
+            | ColumnType::Text
+            | ColumnType::NText
+            | ColumnType::Xml => self
+                .row
+                .try_get::<&str, _>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::String(v.to_string())),
+            ColumnType::BigBinary | ColumnType::BigVarBin | ColumnType::Image => self
+                .row
+                .try_get::<&[u8], _>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::Bytes(v.to_vec())),
+            ColumnType::Datetime | ColumnType::Datetime4 | ColumnType::Datetimen | ColumnType::Datetime2 => self
+                .row
+                .try_get::<chrono::NaiveDateTime, _>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::DateTime(chrono::DateTime::from_naive_utc_and_offset(v, chrono::Utc))),
+            // `DATETIMEOFFSET`带独立的时区偏移，不能像`Datetime2`那样假定UTC
+            ColumnType::DatetimeOffsetn => self
+                .row
+                .try_get::<chrono::DateTime<chrono::FixedOffset>, _>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::DateTimeTz),
+            ColumnType::Daten => self
+                .row
+                .try_get::<chrono::NaiveDate, _>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Date),
+            ColumnType::Timen => self
+                .row
+                .try_get::<chrono::NaiveTime, _>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Time),
+            ColumnType::Null => None,
+            // 未覆盖的类型（如UDT/SqlVariant）不猜测，按字符串兜底
+            _ => self
+                .row
+                .try_get::<&str, _>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::String(v.to_string())),
         }
-        
-        // 如果都失败了，返回None
-        None
     }
 
     fn get_value_by_name(&self, name: &str) -> Option<DatabaseValue> {
@@ -86,7 +137,7 @@ impl DatabaseRow for MSSQLRow {
 
     fn to_map(&self) -> HashMap<String, DatabaseValue> {
         let mut map = HashMap::new();
-        
+
         for (i, name) in self.column_names.iter().enumerate() {
             if let Some(value) = self.get_value(i) {
                 map.insert(name.clone(), value);
@@ -97,7 +148,39 @@ impl DatabaseRow for MSSQLRow {
     }
 }
 
-impl MSSQLRow {
+/// 将TDS列类型映射为Polars `DataType`，不依赖任何具体的行数据
+///
+/// 与 `MSSQLRow::get_value` 按`ColumnType`分组选择`try_get`的方式保持同一套分组，
+/// 供 `MSSQLPool::describe` 在完全不物化结果集的情况下推断列结构。
+pub(crate) fn column_type_to_dtype(column_type: ColumnType) -> DataType {
+    match column_type {
+        ColumnType::Bit | ColumnType::Bitn => DataType::Boolean,
+        ColumnType::Int1 | ColumnType::Int2 | ColumnType::Int4 => DataType::Int32,
+        ColumnType::Int8 | ColumnType::Intn => DataType::Int64,
+        ColumnType::Float4 => DataType::Float32,
+        ColumnType::Float8 | ColumnType::Floatn => DataType::Float64,
+        ColumnType::Money | ColumnType::Money4 | ColumnType::Decimaln | ColumnType::Numericn => {
+            DataType::Decimal(None, None)
+        }
+        ColumnType::Guid => DataType::String,
+        ColumnType::BigVarChar
+        | ColumnType::BigChar
+        | ColumnType::NVarchar
+        | ColumnType::NChar
+        | ColumnType::Text
+        | ColumnType::NText
+        | ColumnType::Xml => DataType::String,
+        ColumnType::BigBinary | ColumnType::BigVarBin | ColumnType::Image => DataType::Binary,
+        ColumnType::Datetime | ColumnType::Datetime4 | ColumnType::Datetimen | ColumnType::Datetime2 => {
+            DataType::Datetime(TimeUnit::Milliseconds, None)
+        }
+        ColumnType::DatetimeOffsetn => DataType::String,
+        ColumnType::Daten => DataType::Date,
+        ColumnType::Timen => DataType::Time,
+        ColumnType::Null => DataType::Null,
+        // 未覆盖的类型（如UDT/SqlVariant）与`get_value`一致，按字符串兜底
+        _ => DataType::String,
+    }
 }
 
 /// MSSQL类型转换器
@@ -157,6 +240,18 @@ impl TypeConverter for MSSQLTypeConverter {
                 )
             }
             DatabaseValue::Uuid(u) => AnyValue::StringOwned(u.to_string().into()),
+            DatabaseValue::Decimal(d) => AnyValue::Decimal(d.mantissa(), d.scale() as usize),
+            DatabaseValue::Date(d) => {
+                let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid epoch date");
+                AnyValue::Date((d - epoch).num_days() as i32)
+            }
+            DatabaseValue::Time(t) => {
+                let midnight = chrono::NaiveTime::from_hms_opt(0, 0, 0).expect("valid midnight");
+                AnyValue::Time(t.signed_duration_since(midnight).num_nanoseconds().unwrap_or(0))
+            }
+            // Polars的`AnyValue::Datetime`按列共享一个时区，单值里放不下`DATETIMEOFFSET`
+            // 各自独立的偏移量，因此退化为RFC3339字符串，保留完整的offset信息
+            DatabaseValue::DateTimeTz(dt) => AnyValue::StringOwned(dt.to_rfc3339().into()),
         }
     }
 
@@ -257,6 +352,45 @@ impl MSSQLTypeConverter {
                 Ok(Series::new(name, datetime_values).cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
                     .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
             }
+            DataType::Decimal(_, scale) => {
+                let int_values: Vec<Option<i128>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Decimal(mantissa, _) => Some(*mantissa),
+                        AnyValue::Null => None,
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, int_values)
+                    .cast(&DataType::Decimal(None, scale))
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
+            DataType::Date => {
+                let date_values: Vec<Option<i32>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Date(d) => Some(*d),
+                        AnyValue::Null => None,
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, date_values)
+                    .cast(&DataType::Date)
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
+            DataType::Time => {
+                let time_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Time(t) => Some(*t),
+                        AnyValue::Null => None,
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, time_values)
+                    .cast(&DataType::Time)
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
             _ => {
                 // 默认转换为字符串
                 let string_values: Vec<Option<String>> = values
@@ -285,10 +419,35 @@ impl MSSQLTypeConverter {
                     return DataType::Datetime(*time_unit, None);
                 }
                 AnyValue::Binary(_) | AnyValue::BinaryOwned(_) => return DataType::Binary,
+                // 精度/小数位以列内第一个非空值为准，与上面按首值定类型的策略一致
+                AnyValue::Decimal(_, scale) => return DataType::Decimal(None, Some(*scale)),
+                AnyValue::Date(_) => return DataType::Date,
+                AnyValue::Time(_) => return DataType::Time,
                 AnyValue::Null => continue,
                 _ => return DataType::String,
             }
         }
         DataType::Null
     }
+
+    /// `database_value_to_any_value`的逆过程：把写回DataFrame某一格的`AnyValue`
+    /// 映射为tiberius批量插入所需的`ColumnData`，供`MSSQLConnection::bulk_insert_dataframe`使用
+    pub(crate) fn any_value_to_column_data(value: AnyValue) -> ColumnData<'static> {
+        match value {
+            AnyValue::Null => ColumnData::I32(None),
+            AnyValue::Boolean(b) => ColumnData::Bit(Some(b)),
+            AnyValue::Int32(i) => ColumnData::I32(Some(i)),
+            AnyValue::Int64(i) => ColumnData::I64(Some(i)),
+            AnyValue::Float32(f) => ColumnData::F32(Some(f)),
+            AnyValue::Float64(f) => ColumnData::F64(Some(f)),
+            AnyValue::String(s) => ColumnData::String(Some(s.to_string().into())),
+            AnyValue::StringOwned(s) => ColumnData::String(Some(s.to_string().into())),
+            AnyValue::Binary(b) => ColumnData::Binary(Some(b.to_vec().into())),
+            AnyValue::BinaryOwned(b) => ColumnData::Binary(Some(b.into())),
+            // 与`database_value_to_tiberius_param`一致，时间列按毫秒时间戳落地，
+            // 不还原为TDS的DATETIME2类型
+            AnyValue::Datetime(ms, _, _) => ColumnData::I64(Some(ms)),
+            other => ColumnData::String(Some(format!("{:?}", other).into())),
+        }
+    }
 }
\ No newline at end of file
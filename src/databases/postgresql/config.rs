@@ -0,0 +1,80 @@
+use crate::core::error::Result;
+use crate::core::types::DatabaseConfig;
+use serde::{Deserialize, Serialize};
+
+/// PostgreSQL数据库特定配置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostgreSQLConfig {
+    pub host: String,
+    pub port: u16,
+    pub database: String,
+    pub username: String,
+    pub password: String,
+    pub connect_timeout: u64,
+}
+
+impl PostgreSQLConfig {
+    pub fn from_database_config(config: &DatabaseConfig) -> Result<Self> {
+        Ok(PostgreSQLConfig {
+            host: config.host.clone(),
+            port: config.port,
+            database: config.database.clone(),
+            username: config.username.clone(),
+            password: config.password.clone(),
+            connect_timeout: config.timeout_config.connection_timeout.as_secs(),
+        })
+    }
+
+    /// 拼接为`tokio_postgres::Config`可解析的libpq风格连接串
+    ///
+    /// `host`/`dbname`/`user`/`password`每个都按libpq的`'...'`引用规则转义后
+    /// 输出，而不是裸拼接：否则值里随便一个空格、单引号或反斜杠（完全正常的
+    /// 密码字符）就会破坏连接串的分词，或被`tokio_postgres`误解析成别的键
+    pub fn to_connection_string(&self) -> String {
+        format!(
+            "host={} port={} dbname={} user={} password={} connect_timeout={}",
+            quote_libpq_value(&self.host),
+            self.port,
+            quote_libpq_value(&self.database),
+            quote_libpq_value(&self.username),
+            quote_libpq_value(&self.password),
+            self.connect_timeout
+        )
+    }
+}
+
+/// 按libpq连接串的`'...'`引用规则转义一个keyword/value值：单引号转义成`\'`，
+/// 反斜杠转义成`\\`，再整体包一层单引号——对空值、纯数字等同样安全，因此无
+/// 条件引用，不做"看起来安全就不引用"的特判
+fn quote_libpq_value(value: &str) -> String {
+    let escaped = value.replace('\\', "\\\\").replace('\'', "\\'");
+    format!("'{}'", escaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with_password(password: &str) -> PostgreSQLConfig {
+        PostgreSQLConfig {
+            host: "localhost".to_string(),
+            port: 5432,
+            database: "app".to_string(),
+            username: "app_user".to_string(),
+            password: password.to_string(),
+            connect_timeout: 5,
+        }
+    }
+
+    #[test]
+    fn quotes_password_containing_space() {
+        let conninfo = config_with_password("has space").to_connection_string();
+        assert!(conninfo.contains("password='has space'"));
+    }
+
+    #[test]
+    fn escapes_quote_and_backslash_in_password() {
+        let conninfo = config_with_password(r"O'Brien\pass").to_connection_string();
+        assert!(conninfo.contains(r"password='O\'Brien\\pass'"));
+    }
+}
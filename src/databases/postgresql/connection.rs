@@ -0,0 +1,358 @@
+use crate::core::error::{QueryError, Result};
+use crate::core::types::{DatabaseValue, QueryParams};
+use crate::databases::postgresql::error::classify_postgres_error;
+use crate::databases::postgresql::types::PostgreSQLRow;
+use crate::databases::traits::{DatabaseConnection, DatabaseRow};
+use futures::TryStreamExt;
+use std::collections::HashMap;
+use tokio::task::JoinHandle;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::Client;
+
+/// PostgreSQL数据库连接
+///
+/// `tokio_postgres::connect`把实际的网络IO驱动拆成一个独立的`Connection` future，
+/// 必须有人持续`poll`它消息才能收发，这里用`tokio::spawn`把它放到后台任务里，
+/// 随连接一起持有`JoinHandle`，在`close`时一并中止。
+pub struct PostgreSQLConnection {
+    client: Option<Client>,
+    driver: Option<JoinHandle<()>>,
+    in_transaction: bool,
+}
+
+impl PostgreSQLConnection {
+    pub fn new(client: Client, driver: JoinHandle<()>) -> Self {
+        Self {
+            client: Some(client),
+            driver: Some(driver),
+            in_transaction: false,
+        }
+    }
+
+    fn convert_params(params: Option<QueryParams>) -> Vec<(String, DatabaseValue)> {
+        params.map(|p| p.into_iter().collect()).unwrap_or_default()
+    }
+
+    /// 将`DatabaseValue`转换为tokio-postgres支持的参数类型
+    fn database_value_to_postgres_param(value: DatabaseValue) -> Box<dyn ToSql + Sync + Send> {
+        match value {
+            DatabaseValue::Null => Box::new(Option::<i32>::None),
+            DatabaseValue::Bool(b) => Box::new(b),
+            DatabaseValue::I32(i) => Box::new(i),
+            DatabaseValue::I64(i) => Box::new(i),
+            DatabaseValue::F32(f) => Box::new(f),
+            DatabaseValue::F64(f) => Box::new(f),
+            DatabaseValue::String(s) => Box::new(s),
+            DatabaseValue::Bytes(b) => Box::new(b),
+            DatabaseValue::DateTime(dt) => Box::new(dt),
+            DatabaseValue::Uuid(u) => Box::new(u),
+            DatabaseValue::Decimal(d) => Box::new(d),
+            DatabaseValue::Date(d) => Box::new(d),
+            DatabaseValue::Time(t) => Box::new(t),
+            // PostgreSQL的TIMESTAMPTZ总是以UTC落地，绑定前先统一时区
+            DatabaseValue::DateTimeTz(dt) => Box::new(dt.with_timezone(&chrono::Utc)),
+        }
+    }
+
+    /// 把SQL中形如`:name`的具名占位符替换为PostgreSQL的位置占位符`$n`，
+    /// 同一个名字在同一条SQL里重复出现时复用同一个`$n`
+    ///
+    /// 不会识别出现在字符串字面量内部的`:name`，调用方应避免在SQL字面量
+    /// 文本中拼接冒号加标识符的内容。
+    fn bind_named_params(
+        sql: &str,
+        named: &[(String, DatabaseValue)],
+    ) -> Result<(String, Vec<Box<dyn ToSql + Sync + Send>>)> {
+        let chars: Vec<char> = sql.chars().collect();
+        let mut output = String::with_capacity(sql.len());
+        let mut values: Vec<Box<dyn ToSql + Sync + Send>> = Vec::new();
+        let mut placeholder_index: HashMap<String, usize> = HashMap::new();
+
+        let mut i = 0;
+        while i < chars.len() {
+            if chars[i] == ':' && i + 1 < chars.len() && (chars[i + 1].is_alphabetic() || chars[i + 1] == '_') {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let name: String = chars[start..end].iter().collect();
+
+                let idx = if let Some(&idx) = placeholder_index.get(&name) {
+                    idx
+                } else {
+                    let (_, value) = named
+                        .iter()
+                        .find(|(n, _)| n == &name)
+                        .ok_or_else(|| QueryError::ParameterBinding(format!("缺少参数: {}", name)))?;
+                    values.push(Self::database_value_to_postgres_param(value.clone()));
+                    let idx = values.len();
+                    placeholder_index.insert(name, idx);
+                    idx
+                };
+
+                output.push('$');
+                output.push_str(&idx.to_string());
+                i = end;
+            } else {
+                output.push(chars[i]);
+                i += 1;
+            }
+        }
+
+        Ok((output, values))
+    }
+
+    /// 只解析一条查询的结果集列元数据（名称+类型），不执行该查询
+    ///
+    /// `Client::prepare`只做服务端语法分析与规划，不会拉取任何行。
+    pub async fn describe_columns(&mut self, sql: &str) -> Result<Vec<(String, tokio_postgres::types::Type)>> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let statement = client
+            .prepare(sql)
+            .await
+            .map_err(classify_postgres_error)?;
+
+        Ok(statement
+            .columns()
+            .iter()
+            .map(|c| (c.name().to_string(), c.type_().clone()))
+            .collect())
+    }
+
+    /// 按位置绑定参数执行查询：`sql`里的`$1`..`$n`直接按`params`的顺序绑定，
+    /// 不经过`bind_named_params`的`:name`重写
+    ///
+    /// 执行前先`prepare`拿到服务端推断的占位符类型并与`params`逐一核对，
+    /// 在真正发请求前就能拒绝明显的类型不匹配（如把字符串绑给INT4占位符）
+    pub async fn query_positional(&mut self, sql: &str, params: &[DatabaseValue]) -> Result<Vec<PostgreSQLRow>> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let statement = client.prepare(sql).await.map_err(classify_postgres_error)?;
+        Self::check_param_types(statement.params(), params)?;
+
+        let values: Vec<Box<dyn ToSql + Sync + Send>> = params
+            .iter()
+            .cloned()
+            .map(Self::database_value_to_postgres_param)
+            .collect();
+        let value_refs: Vec<&(dyn ToSql + Sync)> = values.iter().map(|v| v.as_ref() as &(dyn ToSql + Sync)).collect();
+
+        let rows = client
+            .query(&statement, &value_refs[..])
+            .await
+            .map_err(classify_postgres_error)?;
+
+        Ok(rows.into_iter().map(PostgreSQLRow::new).collect())
+    }
+
+    /// 核对位置参数个数与类型是否与驱动推断的占位符类型兼容；`Null`对任意占位符
+    /// 类型都放行，交由服务器按列的可空性决定是否接受
+    fn check_param_types(expected: &[tokio_postgres::types::Type], params: &[DatabaseValue]) -> Result<()> {
+        if expected.len() != params.len() {
+            return Err(QueryError::ParameterBinding(format!(
+                "参数个数不匹配：SQL需要{}个占位符，实际传入{}个",
+                expected.len(),
+                params.len()
+            ))
+            .into());
+        }
+
+        for (index, (pg_type, value)) in expected.iter().zip(params.iter()).enumerate() {
+            if !Self::param_type_compatible(pg_type, value) {
+                return Err(QueryError::ParameterBinding(format!(
+                    "第{}个参数类型不匹配：占位符推断为{}，实际传入{:?}",
+                    index + 1,
+                    pg_type,
+                    value
+                ))
+                .into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn param_type_compatible(pg_type: &tokio_postgres::types::Type, value: &DatabaseValue) -> bool {
+        use tokio_postgres::types::Type as PgType;
+
+        if matches!(value, DatabaseValue::Null) {
+            return true;
+        }
+
+        matches!(
+            (pg_type, value),
+            (&PgType::BOOL, DatabaseValue::Bool(_))
+                | (&PgType::INT2, DatabaseValue::I32(_))
+                | (&PgType::INT4, DatabaseValue::I32(_))
+                | (&PgType::INT8, DatabaseValue::I64(_))
+                | (&PgType::FLOAT4, DatabaseValue::F32(_))
+                | (&PgType::FLOAT8, DatabaseValue::F64(_))
+                | (&PgType::NUMERIC, DatabaseValue::Decimal(_))
+                | (&PgType::UUID, DatabaseValue::Uuid(_))
+                | (&PgType::TEXT, DatabaseValue::String(_))
+                | (&PgType::VARCHAR, DatabaseValue::String(_))
+                | (&PgType::BPCHAR, DatabaseValue::String(_))
+                | (&PgType::NAME, DatabaseValue::String(_))
+                | (&PgType::BYTEA, DatabaseValue::Bytes(_))
+                | (&PgType::TIMESTAMP, DatabaseValue::DateTime(_))
+                | (&PgType::TIMESTAMPTZ, DatabaseValue::DateTime(_))
+                | (&PgType::TIMESTAMPTZ, DatabaseValue::DateTimeTz(_))
+                | (&PgType::DATE, DatabaseValue::Date(_))
+                | (&PgType::TIME, DatabaseValue::Time(_))
+        )
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseConnection for PostgreSQLConnection {
+    type Row = PostgreSQLRow;
+
+    async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<Vec<Self::Row>> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let named = Self::convert_params(params);
+        let (bound_sql, values) = Self::bind_named_params(sql, &named)?;
+        let value_refs: Vec<&(dyn ToSql + Sync)> = values.iter().map(|v| v.as_ref() as &(dyn ToSql + Sync)).collect();
+
+        let rows = client
+            .query(&bound_sql, &value_refs[..])
+            .await
+            .map_err(classify_postgres_error)?;
+
+        Ok(rows.into_iter().map(PostgreSQLRow::new).collect())
+    }
+
+    async fn query_stream(
+        &mut self,
+        sql: &str,
+        params: Option<QueryParams>,
+        buffer_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<Self::Row>>> {
+        let client = self.client.take().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let named = Self::convert_params(params);
+        let (bound_sql, values) = Self::bind_named_params(sql, &named)?;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(buffer_size.max(1));
+
+        // 流耗尽（或消费者丢弃`rx`导致发送失败）后`client`随任务一起被丢弃，
+        // 不会还给`self.client`：`self`之后不能再发起新的查询，需调用方重新从
+        // 连接池取一个连接
+        tokio::spawn(async move {
+            let value_refs: Vec<&(dyn ToSql + Sync)> =
+                values.iter().map(|v| v.as_ref() as &(dyn ToSql + Sync)).collect();
+
+            let mut stream = match client.query_raw(&bound_sql, value_refs).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let _ = tx.send(Err(classify_postgres_error(e))).await;
+                    return;
+                }
+            };
+
+            loop {
+                match stream.try_next().await {
+                    Ok(Some(row)) => {
+                        if tx.send(Ok(PostgreSQLRow::new(row))).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(None) => return,
+                    Err(e) => {
+                        let _ = tx.send(Err(classify_postgres_error(e))).await;
+                        return;
+                    }
+                }
+            }
+        });
+
+        Ok(rx)
+    }
+
+    async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        let named = Self::convert_params(params);
+        let (bound_sql, values) = Self::bind_named_params(sql, &named)?;
+        let value_refs: Vec<&(dyn ToSql + Sync)> = values.iter().map(|v| v.as_ref() as &(dyn ToSql + Sync)).collect();
+
+        let affected = client
+            .execute(&bound_sql, &value_refs[..])
+            .await
+            .map_err(classify_postgres_error)?;
+
+        Ok(affected)
+    }
+
+    async fn begin_transaction(&mut self) -> Result<()> {
+        if self.in_transaction {
+            return Err(QueryError::ExecutionFailed("已在事务中".to_string()).into());
+        }
+
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        client
+            .batch_execute("BEGIN")
+            .await
+            .map_err(classify_postgres_error)?;
+
+        self.in_transaction = true;
+        Ok(())
+    }
+
+    async fn commit_transaction(&mut self) -> Result<()> {
+        if !self.in_transaction {
+            return Err(QueryError::ExecutionFailed("不在事务中".to_string()).into());
+        }
+
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        client
+            .batch_execute("COMMIT")
+            .await
+            .map_err(classify_postgres_error)?;
+
+        self.in_transaction = false;
+        Ok(())
+    }
+
+    async fn rollback_transaction(&mut self) -> Result<()> {
+        if !self.in_transaction {
+            return Err(QueryError::ExecutionFailed("不在事务中".to_string()).into());
+        }
+
+        let client = self.client.as_mut().ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()))?;
+
+        client
+            .batch_execute("ROLLBACK")
+            .await
+            .map_err(classify_postgres_error)?;
+
+        self.in_transaction = false;
+        Ok(())
+    }
+
+    async fn is_valid(&mut self) -> bool {
+        if let Some(client) = self.client.as_mut() {
+            client.simple_query("SELECT 1").await.is_ok()
+        } else {
+            false
+        }
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.in_transaction {
+            let _ = self.rollback_transaction().await;
+        }
+
+        // `Client`被drop后后台驱动任务会自行退出，这里显式abort以立即释放资源
+        self.client.take();
+        if let Some(driver) = self.driver.take() {
+            driver.abort();
+        }
+
+        Ok(())
+    }
+}
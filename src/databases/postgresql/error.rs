@@ -0,0 +1,29 @@
+use crate::core::error::DbPoolError;
+use crate::core::sqlstate;
+
+/// 把tokio-postgres错误分类为`DbPoolError::Database`；拿不到SQLSTATE（如IO/协议
+/// 层错误、建连阶段失败）时退化为`QueryError::ExecutionFailed`字符串兜底，
+/// 与`mssql::error::classify_query_error`对`tiberius::error::Error`的处理方式一致
+///
+/// `severity`/`detail`/`constraint`/`table`/`column`取自`err.as_db_error()`——
+/// tokio-postgres已经把服务端`ErrorResponse`消息里的`S`/`D`/`n`/`t`/`c`字段解析
+/// 成了强类型的`DbError`，不需要再自己遍历原始`ErrorFields`
+pub fn classify_postgres_error(err: tokio_postgres::Error) -> DbPoolError {
+    match err.code() {
+        Some(code) => {
+            let (sqlstate, category) = sqlstate::classify(code.code());
+            let db_error = err.as_db_error();
+            DbPoolError::Database {
+                sqlstate,
+                category,
+                message: err.to_string(),
+                severity: db_error.map(|e| e.severity().to_string()),
+                detail: db_error.and_then(|e| e.detail()).map(str::to_string),
+                constraint: db_error.and_then(|e| e.constraint()).map(str::to_string),
+                table: db_error.and_then(|e| e.table()).map(str::to_string),
+                column: db_error.and_then(|e| e.column()).map(str::to_string),
+            }
+        }
+        None => crate::core::error::QueryError::ExecutionFailed(err.to_string()).into(),
+    }
+}
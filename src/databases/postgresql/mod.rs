@@ -0,0 +1,10 @@
+pub mod config;
+pub mod connection;
+pub mod error;
+pub mod pool;
+pub mod types;
+
+pub use connection::PostgreSQLConnection;
+pub use error::classify_postgres_error;
+pub use pool::PostgreSQLPool;
+pub use types::PostgreSQLRow;
@@ -0,0 +1,568 @@
+use crate::core::error::{ConnectionError, DbPoolError, Result};
+use crate::core::types::{
+    BatchOperation, BatchResult, DatabaseType, DatabaseValue, PoolStatus, QueryParams, ResultFormat,
+};
+use crate::databases::postgresql::config::PostgreSQLConfig;
+use crate::databases::postgresql::connection::PostgreSQLConnection;
+use crate::databases::postgresql::types::PostgreSQLRow;
+use crate::databases::traits::{DatabaseConnection, DatabasePool, DatabaseRow, TransactionSession, TypeConverter};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::sync::{RwLock, Semaphore};
+use tokio_postgres::NoTls;
+use tokio_postgres_rustls::MakeRustlsConnect;
+
+/// 最近一次建连/健康探测的结果，供`get_status`汇报`is_healthy`/`last_error`
+struct HealthState {
+    is_healthy: bool,
+    last_error: Option<String>,
+}
+
+/// PostgreSQL连接池
+pub struct PostgreSQLPool {
+    config: crate::core::types::DatabaseConfig,
+    pg_config: PostgreSQLConfig,
+    connections: Arc<RwLock<VecDeque<PostgreSQLConnection>>>,
+    semaphore: Arc<Semaphore>,
+    total_connections: Arc<RwLock<u32>>,
+    /// 当前阻塞在`get_connection`的`semaphore.acquire()`里的任务数
+    waiting_acquires: Arc<AtomicU32>,
+    health: Arc<RwLock<HealthState>>,
+    created_at: Instant,
+}
+
+impl PostgreSQLPool {
+    /// 创建新的PostgreSQL连接池
+    pub async fn new(config: &crate::core::types::DatabaseConfig) -> Result<Self> {
+        Self::validate_config(config)?;
+        let pg_config = PostgreSQLConfig::from_database_config(config)?;
+
+        let pool = Self {
+            config: config.clone(),
+            pg_config,
+            connections: Arc::new(RwLock::new(VecDeque::new())),
+            semaphore: Arc::new(Semaphore::new(config.pool_config.max_connections as usize)),
+            total_connections: Arc::new(RwLock::new(0)),
+            waiting_acquires: Arc::new(AtomicU32::new(0)),
+            health: Arc::new(RwLock::new(HealthState {
+                is_healthy: true,
+                last_error: None,
+            })),
+            created_at: Instant::now(),
+        };
+
+        pool.ensure_min_connections().await?;
+        Ok(pool)
+    }
+
+    /// 验证配置
+    pub fn validate_config(config: &crate::core::types::DatabaseConfig) -> Result<()> {
+        if config.db_type != DatabaseType::PostgreSQL {
+            return Err(DbPoolError::Runtime("配置类型不是PostgreSQL".to_string()));
+        }
+
+        if config.host.is_empty() {
+            return Err(DbPoolError::Runtime("PostgreSQL主机地址不能为空".to_string()));
+        }
+
+        if config.database.is_empty() {
+            return Err(DbPoolError::Runtime("PostgreSQL数据库名不能为空".to_string()));
+        }
+
+        if config.username.is_empty() {
+            return Err(DbPoolError::Runtime("PostgreSQL用户名不能为空".to_string()));
+        }
+
+        // 把`ssl_mode`/`trust_server_certificate`/`certificate_path`的组合判断
+        // 都交给`core::tls::resolve_policy`，这里只负责把解析错误透出去
+        if let Some(ssl_config) = &config.ssl_config {
+            crate::core::tls::resolve_policy(ssl_config)?;
+        }
+
+        Ok(())
+    }
+
+    /// 确保最小连接数
+    async fn ensure_min_connections(&self) -> Result<()> {
+        let current_count = {
+            let connections = self.connections.read().await;
+            connections.len() as u32
+        };
+
+        let min_connections = self.config.pool_config.min_connections;
+        if current_count < min_connections {
+            let needed = min_connections - current_count;
+            for _ in 0..needed {
+                let connection = self.create_connection().await?;
+                {
+                    let mut connections = self.connections.write().await;
+                    connections.push_back(connection);
+                }
+                {
+                    let mut total = self.total_connections.write().await;
+                    *total += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 创建新连接
+    async fn create_connection(&self) -> Result<PostgreSQLConnection> {
+        let policy = match &self.config.ssl_config {
+            Some(ssl_config) => crate::core::tls::resolve_policy(ssl_config)?,
+            None => crate::core::tls::TlsVerificationPolicy::Disabled,
+        };
+
+        let connect_result = tokio::time::timeout(
+            self.config.timeout_config.connection_timeout,
+            Self::connect_with_policy(&self.pg_config.to_connection_string(), &policy),
+        )
+        .await
+        .map_err(|_| ConnectionError::AcquireTimeout)?;
+
+        match connect_result {
+            Ok((client, driver)) => {
+                self.record_health(true, None).await;
+                Ok(PostgreSQLConnection::new(client, driver))
+            }
+            Err(e) => {
+                self.record_health(false, Some(e.to_string())).await;
+                Err(e)
+            }
+        }
+    }
+
+    /// 依据解析后的TLS策略建连：`Disabled`走原有的`NoTls`路径，其余策略通过
+    /// `tokio-postgres-rustls`把`core::tls::build_client_config`产出的
+    /// `ClientConfig`接到`tokio_postgres::connect`上
+    async fn connect_with_policy(
+        conninfo: &str,
+        policy: &crate::core::tls::TlsVerificationPolicy,
+    ) -> Result<(tokio_postgres::Client, tokio::task::JoinHandle<()>)> {
+        if matches!(policy, crate::core::tls::TlsVerificationPolicy::Disabled) {
+            let (client, connection) = tokio_postgres::connect(conninfo, NoTls)
+                .await
+                .map_err(|e| ConnectionError::ConnectionFailed(e.to_string()))?;
+            let driver = tokio::spawn(async move {
+                let _ = connection.await;
+            });
+            return Ok((client, driver));
+        }
+
+        let tls_config = crate::core::tls::build_client_config(policy)?;
+        let connector = MakeRustlsConnect::new((*tls_config).clone());
+        let (client, connection) = tokio_postgres::connect(conninfo, connector)
+            .await
+            .map_err(|e| ConnectionError::ConnectionFailed(e.to_string()))?;
+        // 驱动连接的后台任务：负责实际的网络收发，连接本身不会自己推进
+        let driver = tokio::spawn(async move {
+            let _ = connection.await;
+        });
+        Ok((client, driver))
+    }
+
+    /// 记录最近一次建连/健康探测的结果，供`get_status`汇报
+    async fn record_health(&self, is_healthy: bool, last_error: Option<String>) {
+        let mut health = self.health.write().await;
+        health.is_healthy = is_healthy;
+        health.last_error = last_error;
+    }
+
+    /// 获取连接
+    async fn get_connection(&self) -> Result<PostgreSQLConnection> {
+        self.waiting_acquires.fetch_add(1, Ordering::SeqCst);
+        let permit_result = tokio::time::timeout(
+            self.config.pool_config.acquire_timeout,
+            self.semaphore.acquire(),
+        )
+        .await;
+        self.waiting_acquires.fetch_sub(1, Ordering::SeqCst);
+
+        let _permit = permit_result
+            .map_err(|_| ConnectionError::AcquireTimeout)?
+            .map_err(|_| ConnectionError::PoolExhausted)?;
+
+        {
+            let mut connections = self.connections.write().await;
+            if let Some(mut connection) = connections.pop_front() {
+                if connection.is_valid().await {
+                    return Ok(connection);
+                }
+                // 连接已失效，丢弃前扣减total_connections，否则计数会只增不减，
+                // 最终导致即便实际连接数低于max_connections也误报PoolExhausted
+                let mut total = self.total_connections.write().await;
+                if *total > 0 {
+                    *total -= 1;
+                }
+            }
+        }
+
+        let total_connections = {
+            let guard = self.total_connections.read().await;
+            *guard
+        };
+
+        if total_connections < self.config.pool_config.max_connections {
+            let connection = self.create_connection().await?;
+            {
+                let mut total = self.total_connections.write().await;
+                *total += 1;
+            }
+            Ok(connection)
+        } else {
+            Err(ConnectionError::PoolExhausted.into())
+        }
+    }
+
+    /// 归还连接
+    async fn return_connection(&self, mut connection: PostgreSQLConnection) {
+        if connection.is_valid().await {
+            let mut connections = self.connections.write().await;
+            connections.push_back(connection);
+        } else {
+            let mut total = self.total_connections.write().await;
+            if *total > 0 {
+                *total -= 1;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabasePool for PostgreSQLPool {
+    async fn execute_query(&self, sql: &str, params: Option<QueryParams>) -> Result<polars::frame::DataFrame> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query(sql, params).await;
+        self.return_connection(connection).await;
+
+        match result {
+            Ok(rows) => {
+                crate::databases::postgresql::types::PostgreSQLTypeConverter::rows_to_dataframe(rows)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn execute_query_rows(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+    ) -> Result<Vec<Box<dyn DatabaseRow>>> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query(sql, params).await;
+        self.return_connection(connection).await;
+
+        result.map(|rows| {
+            rows.into_iter()
+                .map(|row| Box::new(row) as Box<dyn DatabaseRow>)
+                .collect()
+        })
+    }
+
+    /// 真正的惰性游标实现：借`DatabaseConnection::query_stream`逐行拉取，每凑够
+    /// `batch_size`行就转换成一个`DataFrame`批次发出，不会先把整个结果集
+    /// 物化在内存里
+    async fn execute_query_stream(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+        batch_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<polars::frame::DataFrame>>> {
+        let batch_size = batch_size.max(1);
+        let mut connection = self.get_connection().await?;
+        let mut row_rx = connection.query_stream(sql, params, batch_size * 2).await?;
+        // `query_stream`已经取走了底层`client`，这里只是让`return_connection`
+        // 观察到`is_valid()==false`从而正确扣减`total_connections`，而不是把
+        // 一个已被取走client的连接放回可复用队列
+        self.return_connection(connection).await;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        tokio::spawn(async move {
+            let mut buffer = Vec::with_capacity(batch_size);
+            while let Some(row_result) = row_rx.recv().await {
+                match row_result {
+                    Ok(row) => {
+                        buffer.push(row);
+                        if buffer.len() >= batch_size {
+                            let chunk = std::mem::take(&mut buffer);
+                            let batch = crate::databases::postgresql::types::PostgreSQLTypeConverter::rows_to_dataframe(chunk);
+                            if tx.send(batch).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+            }
+            if !buffer.is_empty() {
+                let batch = crate::databases::postgresql::types::PostgreSQLTypeConverter::rows_to_dataframe(buffer);
+                let _ = tx.send(batch).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
+    async fn execute_non_query(&self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.execute(sql, params).await;
+        self.return_connection(connection).await;
+        result
+    }
+
+    /// 按位置绑定参数执行查询：`sql`使用PostgreSQL原生的`$1`..`$n`占位符，参数
+    /// 类型在执行前就与驱动推断的占位符类型核对过
+    ///
+    /// `ResultFormat::Binary`复用`execute_query`已有的按列类型精确解码路径；
+    /// `ResultFormat::Text`不管原始列类型，统一把每个值物化为字符串
+    async fn query_with_params(
+        &self,
+        sql: &str,
+        params: &[DatabaseValue],
+        result_format: ResultFormat,
+    ) -> Result<polars::frame::DataFrame> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query_positional(sql, params).await;
+        self.return_connection(connection).await;
+        let rows = result?;
+
+        match result_format {
+            ResultFormat::Binary => {
+                crate::databases::postgresql::types::PostgreSQLTypeConverter::rows_to_dataframe(rows)
+            }
+            ResultFormat::Text => crate::databases::traits::rows_to_text_dataframe(rows),
+        }
+    }
+
+    async fn execute_batch(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>> {
+        let mut connection = self.get_connection().await?;
+        let mut results = Vec::new();
+
+        for operation in operations {
+            let start_time = Instant::now();
+            let result = connection.execute(&operation.sql, operation.params).await;
+            let execution_time = start_time.elapsed();
+
+            match result {
+                Ok(affected_rows) => {
+                    results.push(BatchResult {
+                        affected_rows,
+                        execution_time,
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    results.push(BatchResult {
+                        affected_rows: 0,
+                        execution_time,
+                        error: Some(e.to_string()),
+                    });
+                }
+            }
+        }
+
+        self.return_connection(connection).await;
+        Ok(results)
+    }
+
+    async fn execute_transaction(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>> {
+        let mut connection = self.get_connection().await?;
+        let mut results = Vec::new();
+
+        connection.begin_transaction().await?;
+
+        let mut transaction_failed = false;
+
+        for operation in operations {
+            let start_time = Instant::now();
+            let result = connection.execute(&operation.sql, operation.params).await;
+            let execution_time = start_time.elapsed();
+
+            match result {
+                Ok(affected_rows) => {
+                    results.push(BatchResult {
+                        affected_rows,
+                        execution_time,
+                        error: None,
+                    });
+                }
+                Err(e) => {
+                    results.push(BatchResult {
+                        affected_rows: 0,
+                        execution_time,
+                        error: Some(e.to_string()),
+                    });
+                    transaction_failed = true;
+                    break;
+                }
+            }
+        }
+
+        if transaction_failed {
+            connection.rollback_transaction().await?;
+        } else {
+            connection.commit_transaction().await?;
+        }
+
+        self.return_connection(connection).await;
+        Ok(results)
+    }
+
+    async fn begin_session(&self) -> Result<Box<dyn TransactionSession>> {
+        let mut connection = self.get_connection().await?;
+        connection.begin_transaction().await?;
+
+        Ok(Box::new(PostgreSQLTransactionSession {
+            connection: Some(connection),
+            total_connections: Arc::clone(&self.total_connections),
+            savepoint_counter: 0,
+        }))
+    }
+
+    /// 获取一条查询结果集的列名与推断类型，不执行该查询
+    async fn describe(&self, sql: &str) -> Result<Vec<crate::core::types::ColumnSchema>> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.describe_columns(sql).await;
+        self.return_connection(connection).await;
+
+        result.map(|columns| {
+            columns
+                .into_iter()
+                .map(|(name, pg_type)| crate::core::types::ColumnSchema {
+                    name,
+                    data_type: crate::databases::postgresql::types::pg_type_to_dtype(&pg_type),
+                })
+                .collect()
+        })
+    }
+
+    async fn get_status(&self) -> Result<PoolStatus> {
+        let connections = self.connections.read().await;
+        let total = self.total_connections.read().await;
+        let active = *total - connections.len() as u32;
+        let health = self.health.read().await;
+
+        Ok(PoolStatus {
+            pool_id: "postgresql_pool".to_string(),
+            db_type: DatabaseType::PostgreSQL,
+            total_connections: *total,
+            active_connections: active,
+            idle_connections: connections.len() as u32,
+            waiting_connections: self.waiting_acquires.load(Ordering::SeqCst),
+            is_healthy: health.is_healthy,
+            last_error: health.last_error.clone(),
+            uptime: self.created_at.elapsed(),
+            circuit_state: crate::core::types::CircuitState::Closed, // 由HealthMonitor覆盖
+            consecutive_failures: 0,
+        })
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        match self.execute_query("SELECT 1", None).await {
+            Ok(_) => {
+                self.record_health(true, None).await;
+                Ok(true)
+            }
+            Err(e) => {
+                self.record_health(false, Some(e.to_string())).await;
+                Ok(false)
+            }
+        }
+    }
+
+    async fn close(&self) -> Result<()> {
+        let mut connections = self.connections.write().await;
+
+        while let Some(mut connection) = connections.pop_front() {
+            let _ = connection.close().await;
+        }
+
+        {
+            let mut total = self.total_connections.write().await;
+            *total = 0;
+        }
+
+        Ok(())
+    }
+}
+
+/// 从`PostgreSQLPool`租用的事务会话
+///
+/// 与`MSSQLTransactionSession`同样的简化实现：结束（提交/回滚）后直接关闭
+/// 底层连接而不归还到池，连接计数相应递减。
+struct PostgreSQLTransactionSession {
+    connection: Option<PostgreSQLConnection>,
+    total_connections: Arc<RwLock<u32>>,
+    savepoint_counter: u32,
+}
+
+impl PostgreSQLTransactionSession {
+    fn connection_mut(&mut self) -> Result<&mut PostgreSQLConnection> {
+        self.connection
+            .as_mut()
+            .ok_or_else(|| DbPoolError::Runtime("事务会话已结束".to_string()))
+    }
+
+    async fn finish(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            let _ = connection.close().await;
+            let mut total = self.total_connections.write().await;
+            if *total > 0 {
+                *total -= 1;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionSession for PostgreSQLTransactionSession {
+    async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<polars::frame::DataFrame> {
+        let rows = self.connection_mut()?.query(sql, params).await?;
+        crate::databases::postgresql::types::PostgreSQLTypeConverter::rows_to_dataframe(rows)
+    }
+
+    async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        self.connection_mut()?.execute(sql, params).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.connection_mut()?.commit_transaction().await?;
+        self.finish().await;
+        Ok(())
+    }
+
+    async fn rollback(&mut self) -> Result<()> {
+        self.connection_mut()?.rollback_transaction().await?;
+        self.finish().await;
+        Ok(())
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoint_counter += 1;
+        self.connection_mut()?
+            .execute(&format!("SAVEPOINT {}", name), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.connection_mut()?
+            .execute(&format!("RELEASE SAVEPOINT {}", name), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn rollback_to(&mut self, name: &str) -> Result<()> {
+        self.connection_mut()?
+            .execute(&format!("ROLLBACK TO SAVEPOINT {}", name), None)
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,380 @@
+use crate::core::error::{ConversionError, Result};
+use crate::core::types::DatabaseValue;
+use crate::databases::traits::{DatabaseRow, TypeConverter};
+use polars::prelude::*;
+use std::collections::HashMap;
+use tokio_postgres::types::Type as PgType;
+use tokio_postgres::Row;
+use uuid::Uuid;
+
+/// PostgreSQL行数据
+pub struct PostgreSQLRow {
+    row: Row,
+    column_names: Vec<String>,
+    /// 每列声明的类型OID，随行一起缓存，驱动`get_value`选择唯一匹配的`try_get`，
+    /// 与`MSSQLRow`按`ColumnType`分派的思路一致
+    column_types: Vec<PgType>,
+}
+
+impl PostgreSQLRow {
+    pub fn new(row: Row) -> Self {
+        let column_names = row.columns().iter().map(|c| c.name().to_string()).collect();
+        let column_types = row.columns().iter().map(|c| c.type_().clone()).collect();
+
+        Self { row, column_names, column_types }
+    }
+}
+
+impl DatabaseRow for PostgreSQLRow {
+    fn column_count(&self) -> usize {
+        self.row.len()
+    }
+
+    fn column_names(&self) -> Vec<String> {
+        self.column_names.clone()
+    }
+
+    /// 按该列声明的`PgType`精确选择一种`try_get`，而非依次尝试各Rust类型猜测
+    fn get_value(&self, index: usize) -> Option<DatabaseValue> {
+        let column_type = self.column_types.get(index)?;
+
+        match *column_type {
+            PgType::BOOL => self.row.try_get::<_, Option<bool>>(index).ok().flatten().map(DatabaseValue::Bool),
+            PgType::INT2 => self
+                .row
+                .try_get::<_, Option<i16>>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::I32(v as i32)),
+            PgType::INT4 => self.row.try_get::<_, Option<i32>>(index).ok().flatten().map(DatabaseValue::I32),
+            PgType::INT8 => self.row.try_get::<_, Option<i64>>(index).ok().flatten().map(DatabaseValue::I64),
+            PgType::FLOAT4 => self.row.try_get::<_, Option<f32>>(index).ok().flatten().map(DatabaseValue::F32),
+            PgType::FLOAT8 => self.row.try_get::<_, Option<f64>>(index).ok().flatten().map(DatabaseValue::F64),
+            // `NUMERIC`经`rust_decimal::Decimal`精确取值，不经f64中转丢精度
+            PgType::NUMERIC => self
+                .row
+                .try_get::<_, Option<rust_decimal::Decimal>>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Decimal),
+            PgType::UUID => self.row.try_get::<_, Option<Uuid>>(index).ok().flatten().map(DatabaseValue::Uuid),
+            PgType::TEXT | PgType::VARCHAR | PgType::BPCHAR | PgType::NAME => self
+                .row
+                .try_get::<_, Option<&str>>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::String(v.to_string())),
+            PgType::BYTEA => self
+                .row
+                .try_get::<_, Option<Vec<u8>>>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Bytes),
+            PgType::TIMESTAMP => self
+                .row
+                .try_get::<_, Option<chrono::NaiveDateTime>>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::DateTime(chrono::DateTime::from_naive_utc_and_offset(v, chrono::Utc))),
+            // `TIMESTAMPTZ`在PostgreSQL内部总是以UTC存储，驱动直接给出`DateTime<Utc>`，
+            // 不像MSSQL的`DATETIMEOFFSET`那样需要保留每行各自的偏移量
+            PgType::TIMESTAMPTZ => self
+                .row
+                .try_get::<_, Option<chrono::DateTime<chrono::Utc>>>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::DateTime),
+            PgType::DATE => self
+                .row
+                .try_get::<_, Option<chrono::NaiveDate>>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Date),
+            PgType::TIME => self
+                .row
+                .try_get::<_, Option<chrono::NaiveTime>>(index)
+                .ok()
+                .flatten()
+                .map(DatabaseValue::Time),
+            // 未覆盖的类型（如JSON/数组/自定义枚举）不猜测，按字符串兜底
+            _ => self
+                .row
+                .try_get::<_, Option<&str>>(index)
+                .ok()
+                .flatten()
+                .map(|v| DatabaseValue::String(v.to_string())),
+        }
+    }
+
+    fn get_value_by_name(&self, name: &str) -> Option<DatabaseValue> {
+        let index = self.column_names.iter().position(|n| n == name)?;
+        self.get_value(index)
+    }
+
+    fn to_map(&self) -> HashMap<String, DatabaseValue> {
+        let mut map = HashMap::new();
+
+        for (i, name) in self.column_names.iter().enumerate() {
+            if let Some(value) = self.get_value(i) {
+                map.insert(name.clone(), value);
+            }
+        }
+
+        map
+    }
+}
+
+/// 将PostgreSQL列类型映射为Polars `DataType`，不依赖任何具体的行数据
+///
+/// 与`PostgreSQLRow::get_value`按`PgType`分组选择`try_get`的方式保持同一套分组，
+/// 供`PostgreSQLPool::describe`在完全不执行查询的情况下推断列结构。
+pub(crate) fn pg_type_to_dtype(pg_type: &PgType) -> DataType {
+    match *pg_type {
+        PgType::BOOL => DataType::Boolean,
+        PgType::INT2 | PgType::INT4 => DataType::Int32,
+        PgType::INT8 => DataType::Int64,
+        PgType::FLOAT4 => DataType::Float32,
+        PgType::FLOAT8 => DataType::Float64,
+        PgType::NUMERIC => DataType::Decimal(None, None),
+        PgType::UUID => DataType::String,
+        PgType::TEXT | PgType::VARCHAR | PgType::BPCHAR | PgType::NAME => DataType::String,
+        PgType::BYTEA => DataType::Binary,
+        PgType::TIMESTAMP | PgType::TIMESTAMPTZ => DataType::Datetime(TimeUnit::Milliseconds, None),
+        PgType::DATE => DataType::Date,
+        PgType::TIME => DataType::Time,
+        _ => DataType::String,
+    }
+}
+
+/// PostgreSQL类型转换器
+pub struct PostgreSQLTypeConverter;
+
+impl TypeConverter for PostgreSQLTypeConverter {
+    fn rows_to_dataframe<R: DatabaseRow>(rows: Vec<R>) -> Result<DataFrame> {
+        if rows.is_empty() {
+            return Ok(DataFrame::empty());
+        }
+
+        let column_names = rows[0].column_names();
+        let column_count = column_names.len();
+
+        let mut columns: Vec<Vec<AnyValue>> = vec![Vec::new(); column_count];
+
+        for row in &rows {
+            for (col_idx, _) in column_names.iter().enumerate() {
+                if let Some(value) = row.get_value(col_idx) {
+                    columns[col_idx].push(Self::database_value_to_any_value(value));
+                } else {
+                    columns[col_idx].push(AnyValue::Null);
+                }
+            }
+        }
+
+        let mut df_columns = Vec::new();
+        for (i, col_name) in column_names.iter().enumerate() {
+            let series = Self::create_series_from_values(col_name, &columns[i])?;
+            df_columns.push(series);
+        }
+
+        DataFrame::new(df_columns)
+            .map_err(|e| ConversionError::DataFrameConversion(e.to_string()).into())
+    }
+
+    fn database_value_to_any_value(value: DatabaseValue) -> AnyValue<'static> {
+        match value {
+            DatabaseValue::Null => AnyValue::Null,
+            DatabaseValue::Bool(b) => AnyValue::Boolean(b),
+            DatabaseValue::I32(i) => AnyValue::Int32(i),
+            DatabaseValue::I64(i) => AnyValue::Int64(i),
+            DatabaseValue::F32(f) => AnyValue::Float32(f),
+            DatabaseValue::F64(f) => AnyValue::Float64(f),
+            DatabaseValue::String(s) => AnyValue::StringOwned(s.into()),
+            DatabaseValue::Bytes(b) => AnyValue::BinaryOwned(b),
+            DatabaseValue::DateTime(dt) => AnyValue::Datetime(dt.timestamp_millis(), TimeUnit::Milliseconds, &None),
+            DatabaseValue::Uuid(u) => AnyValue::StringOwned(u.to_string().into()),
+            DatabaseValue::Decimal(d) => AnyValue::Decimal(d.mantissa(), d.scale() as usize),
+            DatabaseValue::Date(d) => {
+                let epoch = chrono::NaiveDate::from_ymd_opt(1970, 1, 1).expect("valid epoch date");
+                AnyValue::Date((d - epoch).num_days() as i32)
+            }
+            DatabaseValue::Time(t) => {
+                let midnight = chrono::NaiveTime::from_hms_opt(0, 0, 0).expect("valid midnight");
+                AnyValue::Time(t.signed_duration_since(midnight).num_nanoseconds().unwrap_or(0))
+            }
+            // PostgreSQL的`TIMESTAMPTZ`总是规整为UTC存储，不存在MSSQL`DATETIMEOFFSET`
+            // 那种每行独立偏移量需要保留的场景，这里仍按RFC3339退化以兼容跨后端转发
+            DatabaseValue::DateTimeTz(dt) => AnyValue::StringOwned(dt.to_rfc3339().into()),
+        }
+    }
+
+    fn convert_params(params: &crate::core::types::QueryParams) -> Result<Vec<(String, DatabaseValue)>> {
+        Ok(params.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+}
+
+impl PostgreSQLTypeConverter {
+    fn create_series_from_values(name: &str, values: &[AnyValue]) -> Result<Series> {
+        if values.is_empty() {
+            return Ok(Series::new_empty(name, &DataType::Null));
+        }
+
+        let data_type = Self::infer_data_type(values);
+
+        match data_type {
+            DataType::Boolean => {
+                let bool_values: Vec<Option<bool>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Boolean(b) => Some(*b),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, bool_values))
+            }
+            DataType::Int32 => {
+                let int_values: Vec<Option<i32>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Int32(i) => Some(*i),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, int_values))
+            }
+            DataType::Int64 => {
+                let int_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Int64(i) => Some(*i),
+                        AnyValue::Int32(i) => Some(*i as i64),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, int_values))
+            }
+            DataType::Float32 => {
+                let float_values: Vec<Option<f32>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Float32(f) => Some(*f),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, float_values))
+            }
+            DataType::Float64 => {
+                let float_values: Vec<Option<f64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Float64(f) => Some(*f),
+                        AnyValue::Float32(f) => Some(*f as f64),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, float_values))
+            }
+            DataType::String => {
+                let string_values: Vec<Option<String>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::String(s) => Some(s.to_string()),
+                        AnyValue::StringOwned(s) => Some(s.to_string()),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, string_values))
+            }
+            DataType::Binary => {
+                let binary_values: Vec<Option<Vec<u8>>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Binary(b) => Some(b.to_vec()),
+                        AnyValue::BinaryOwned(b) => Some(b.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, binary_values))
+            }
+            DataType::Datetime(TimeUnit::Milliseconds, _) => {
+                let datetime_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Datetime(dt, _, _) => Some(*dt),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, datetime_values)
+                    .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
+            DataType::Decimal(_, scale) => {
+                let int_values: Vec<Option<i128>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Decimal(mantissa, _) => Some(*mantissa),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, int_values)
+                    .cast(&DataType::Decimal(None, scale))
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
+            DataType::Date => {
+                let date_values: Vec<Option<i32>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Date(d) => Some(*d),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, date_values)
+                    .cast(&DataType::Date)
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
+            DataType::Time => {
+                let time_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Time(t) => Some(*t),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, time_values)
+                    .cast(&DataType::Time)
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()))?)
+            }
+            _ => {
+                let string_values: Vec<Option<String>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Null => None,
+                        _ => Some(format!("{:?}", v)),
+                    })
+                    .collect();
+                Ok(Series::new(name, string_values))
+            }
+        }
+    }
+
+    /// 推断数据类型：取列内第一个非空值的类型，精度/小数位同样以首值为准
+    fn infer_data_type(values: &[AnyValue]) -> DataType {
+        for value in values {
+            match value {
+                AnyValue::Boolean(_) => return DataType::Boolean,
+                AnyValue::Int32(_) => return DataType::Int32,
+                AnyValue::Int64(_) => return DataType::Int64,
+                AnyValue::Float32(_) => return DataType::Float32,
+                AnyValue::Float64(_) => return DataType::Float64,
+                AnyValue::String(_) | AnyValue::StringOwned(_) => return DataType::String,
+                AnyValue::Binary(_) | AnyValue::BinaryOwned(_) => return DataType::Binary,
+                AnyValue::Datetime(_, time_unit, _) => return DataType::Datetime(*time_unit, None),
+                AnyValue::Decimal(_, scale) => return DataType::Decimal(None, Some(*scale)),
+                AnyValue::Date(_) => return DataType::Date,
+                AnyValue::Time(_) => return DataType::Time,
+                AnyValue::Null => continue,
+                _ => return DataType::String,
+            }
+        }
+        DataType::Null
+    }
+}
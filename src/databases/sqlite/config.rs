@@ -0,0 +1,22 @@
+use crate::core::error::Result;
+use crate::core::types::DatabaseConfig;
+use serde::{Deserialize, Serialize};
+
+/// SQLite数据库特定配置
+///
+/// SQLite没有网络主机/端口的概念，这里复用 `DatabaseConfig::database`
+/// 字段作为数据库文件路径（例如 `./data/app.db`，或 `:memory:`）。
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SQLiteConfig {
+    pub path: String,
+    pub allow_load_extension: bool,
+}
+
+impl SQLiteConfig {
+    pub fn from_database_config(config: &DatabaseConfig) -> Result<Self> {
+        Ok(SQLiteConfig {
+            path: config.database.clone(),
+            allow_load_extension: config.allow_load_extension,
+        })
+    }
+}
@@ -0,0 +1,307 @@
+use crate::core::error::{QueryError, Result};
+use crate::core::types::{DatabaseValue, QueryParams};
+use crate::databases::sqlite::types::SQLiteRow;
+use crate::databases::traits::{DatabaseConnection, DatabaseRow};
+use rusqlite::Connection;
+
+/// SQLite数据库连接
+///
+/// rusqlite是同步API，这里统一通过 `spawn_blocking` 把实际的SQL执行挪到
+/// 阻塞线程池，避免在tokio的异步运行时里直接做阻塞文件IO。连接本身在
+/// 执行期间被临时移出 `self.conn`，执行完毕后再放回，从而不必为
+/// `rusqlite::Connection`（非`Sync`）额外加锁。
+pub struct SQLiteConnection {
+    conn: Option<Connection>,
+    in_transaction: bool,
+}
+
+impl SQLiteConnection {
+    pub fn new(conn: Connection) -> Self {
+        Self {
+            conn: Some(conn),
+            in_transaction: false,
+        }
+    }
+
+    /// 临时取出底层连接，供需要裸连接的场景（备份、注册函数）使用
+    pub(crate) fn take_conn(&mut self) -> Result<Connection> {
+        self.conn
+            .take()
+            .ok_or_else(|| QueryError::ExecutionFailed("Connection closed".to_string()).into())
+    }
+
+    /// 归还通过 `take_conn` 取出的底层连接
+    pub(crate) fn restore_conn(&mut self, conn: Connection) {
+        self.conn = Some(conn);
+    }
+
+    fn convert_params(params: Option<QueryParams>) -> Vec<(String, DatabaseValue)> {
+        params.map(|p| p.into_iter().collect()).unwrap_or_default()
+    }
+
+    fn bind_params(named: &[(String, DatabaseValue)]) -> Vec<(String, Box<dyn rusqlite::ToSql>)> {
+        named
+            .iter()
+            .map(|(name, value)| {
+                let key = if name.starts_with([':', '@', '$']) {
+                    name.clone()
+                } else {
+                    format!("@{}", name)
+                };
+                let bound: Box<dyn rusqlite::ToSql> = match value {
+                    DatabaseValue::Null => Box::new(Option::<i64>::None),
+                    DatabaseValue::Bool(b) => Box::new(*b),
+                    DatabaseValue::I32(i) => Box::new(*i),
+                    DatabaseValue::I64(i) => Box::new(*i),
+                    DatabaseValue::F32(f) => Box::new(*f as f64),
+                    DatabaseValue::F64(f) => Box::new(*f),
+                    DatabaseValue::String(s) => Box::new(s.clone()),
+                    DatabaseValue::Bytes(b) => Box::new(b.clone()),
+                    DatabaseValue::DateTime(dt) => Box::new(dt.timestamp_millis()),
+                    DatabaseValue::Uuid(u) => Box::new(u.to_string()),
+                    // SQLite没有原生DECIMAL/DATE/TIME/TIMESTAMPTZ类型，按文本存取
+                    DatabaseValue::Decimal(d) => Box::new(d.to_string()),
+                    DatabaseValue::Date(d) => Box::new(d.to_string()),
+                    DatabaseValue::Time(t) => Box::new(t.to_string()),
+                    DatabaseValue::DateTimeTz(dt) => Box::new(dt.to_rfc3339()),
+                };
+                (key, bound)
+            })
+            .collect()
+    }
+
+    fn run_query(conn: &Connection, sql: &str, named: &[(String, DatabaseValue)]) -> Result<Vec<SQLiteRow>> {
+        let bound = Self::bind_params(named);
+        let param_refs: Vec<(&str, &dyn rusqlite::ToSql)> = bound
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_ref()))
+            .collect();
+
+        // prepare_cached命中同一连接按SQL文本维护的LRU语句缓存，
+        // 避免对热点查询重复解析/重新规划执行计划
+        let mut stmt = conn
+            .prepare_cached(sql)
+            .map_err(|e| QueryError::SyntaxError(e.to_string()))?;
+        let column_names: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let mut rows = stmt
+            .query(&param_refs[..])
+            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+
+        let mut result = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .map_err(|e| QueryError::ResultProcessing(e.to_string()))?
+        {
+            result.push(
+                SQLiteRow::from_row(row, &column_names)
+                    .map_err(|e| QueryError::ResultProcessing(e.to_string()))?,
+            );
+        }
+
+        Ok(result)
+    }
+
+    /// `run_query`的流式版本：每解出一行立刻通过`tx.blocking_send`发出，不在
+    /// 内存里攒`Vec<SQLiteRow>`；运行在`spawn_blocking`线程上，因此用阻塞发送
+    fn run_query_streaming(
+        conn: &Connection,
+        sql: &str,
+        named: &[(String, DatabaseValue)],
+        tx: &tokio::sync::mpsc::Sender<Result<SQLiteRow>>,
+    ) {
+        let bound = Self::bind_params(named);
+        let param_refs: Vec<(&str, &dyn rusqlite::ToSql)> = bound
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_ref()))
+            .collect();
+
+        let mut stmt = match conn.prepare_cached(sql) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                let _ = tx.blocking_send(Err(QueryError::SyntaxError(e.to_string()).into()));
+                return;
+            }
+        };
+        let column_names: Vec<String> = stmt
+            .column_names()
+            .into_iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let mut rows = match stmt.query(&param_refs[..]) {
+            Ok(rows) => rows,
+            Err(e) => {
+                let _ = tx.blocking_send(Err(QueryError::ExecutionFailed(e.to_string()).into()));
+                return;
+            }
+        };
+
+        loop {
+            let next = match rows.next() {
+                Ok(next) => next,
+                Err(e) => {
+                    let _ = tx.blocking_send(Err(QueryError::ResultProcessing(e.to_string()).into()));
+                    return;
+                }
+            };
+
+            let Some(row) = next else {
+                return;
+            };
+
+            let row = SQLiteRow::from_row(row, &column_names)
+                .map_err(|e| QueryError::ResultProcessing(e.to_string()).into());
+            let is_err = row.is_err();
+            if tx.blocking_send(row).is_err() || is_err {
+                return;
+            }
+        }
+    }
+
+    fn run_execute(conn: &Connection, sql: &str, named: &[(String, DatabaseValue)]) -> Result<u64> {
+        let bound = Self::bind_params(named);
+        let param_refs: Vec<(&str, &dyn rusqlite::ToSql)> = bound
+            .iter()
+            .map(|(k, v)| (k.as_str(), v.as_ref()))
+            .collect();
+
+        let mut stmt = conn
+            .prepare_cached(sql)
+            .map_err(|e| QueryError::SyntaxError(e.to_string()))?;
+        let affected = stmt
+            .execute(&param_refs[..])
+            .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+
+        Ok(affected as u64)
+    }
+
+    fn run_describe(conn: &Connection, sql: &str) -> Result<Vec<String>> {
+        let stmt = conn
+            .prepare_cached(sql)
+            .map_err(|e| QueryError::SyntaxError(e.to_string()))?;
+        Ok(stmt.column_names().into_iter().map(|s| s.to_string()).collect())
+    }
+
+    /// 预热连接级语句缓存并返回结果列名，供 `DatabasePool::prepare` 使用
+    pub(crate) async fn describe_statement(&mut self, sql: &str) -> Result<Vec<String>> {
+        let conn = self.take_conn()?;
+        let sql = sql.to_string();
+
+        let (conn, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::run_describe(&conn, &sql);
+            (conn, result)
+        })
+        .await
+        .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+
+        self.restore_conn(conn);
+        result
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabaseConnection for SQLiteConnection {
+    type Row = SQLiteRow;
+
+    async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<Vec<Self::Row>> {
+        let conn = self.take_conn()?;
+        let sql = sql.to_string();
+        let named = Self::convert_params(params);
+
+        let (conn, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::run_query(&conn, &sql, &named);
+            (conn, result)
+        })
+        .await
+        .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+
+        self.restore_conn(conn);
+        result
+    }
+
+    async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        let conn = self.take_conn()?;
+        let sql = sql.to_string();
+        let named = Self::convert_params(params);
+
+        let (conn, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::run_execute(&conn, &sql, &named);
+            (conn, result)
+        })
+        .await
+        .map_err(|e| QueryError::ExecutionFailed(e.to_string()))?;
+
+        self.restore_conn(conn);
+        result
+    }
+
+    async fn query_stream(
+        &mut self,
+        sql: &str,
+        params: Option<QueryParams>,
+        buffer_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<Self::Row>>> {
+        let conn = self.take_conn()?;
+        let sql = sql.to_string();
+        let named = Self::convert_params(params);
+        let (tx, rx) = tokio::sync::mpsc::channel(buffer_size.max(1));
+
+        // 流耗尽（或消费者丢弃`rx`导致发送失败）后`conn`随闭包一起被丢弃，
+        // 不会还给`self.conn`：`self`之后不能再发起新的查询，需调用方重新从
+        // 连接池取一个连接
+        tokio::task::spawn_blocking(move || {
+            Self::run_query_streaming(&conn, &sql, &named, &tx);
+        });
+
+        Ok(rx)
+    }
+
+    async fn begin_transaction(&mut self) -> Result<()> {
+        if self.in_transaction {
+            return Err(QueryError::ExecutionFailed("已在事务中".to_string()).into());
+        }
+
+        self.execute("BEGIN TRANSACTION", None).await?;
+        self.in_transaction = true;
+        Ok(())
+    }
+
+    async fn commit_transaction(&mut self) -> Result<()> {
+        if !self.in_transaction {
+            return Err(QueryError::ExecutionFailed("不在事务中".to_string()).into());
+        }
+
+        self.execute("COMMIT", None).await?;
+        self.in_transaction = false;
+        Ok(())
+    }
+
+    async fn rollback_transaction(&mut self) -> Result<()> {
+        if !self.in_transaction {
+            return Err(QueryError::ExecutionFailed("不在事务中".to_string()).into());
+        }
+
+        self.execute("ROLLBACK", None).await?;
+        self.in_transaction = false;
+        Ok(())
+    }
+
+    async fn is_valid(&mut self) -> bool {
+        self.execute("SELECT 1", None).await.is_ok()
+    }
+
+    async fn close(&mut self) -> Result<()> {
+        if self.in_transaction {
+            let _ = self.rollback_transaction().await;
+        }
+
+        // rusqlite::Connection在Drop时会自动关闭底层句柄
+        self.conn = None;
+        Ok(())
+    }
+}
@@ -0,0 +1,8 @@
+pub mod config;
+pub mod connection;
+pub mod pool;
+pub mod types;
+
+pub use pool::SQLitePool;
+pub use connection::SQLiteConnection;
+pub use types::SQLiteRow;
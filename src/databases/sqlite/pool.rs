@@ -0,0 +1,729 @@
+use crate::core::error::{ConnectionError, DbPoolError, Result};
+use crate::core::types::{
+    BatchOperation, BatchResult, DatabaseType, DatabaseValue, PoolStatus, QueryParams,
+};
+use crate::databases::sqlite::config::SQLiteConfig;
+use crate::databases::sqlite::connection::SQLiteConnection;
+use crate::databases::sqlite::types::{SQLiteRow, SQLiteTypeConverter};
+use crate::databases::traits::{DatabaseConnection, DatabasePool, DatabaseRow, StatementHandle, TransactionSession, TypeConverter};
+use rusqlite::Connection;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{RwLock, Semaphore};
+
+/// 用户通过 `register_scalar_function` 注册的标量UDF
+type ScalarFn = Arc<dyn Fn(Vec<DatabaseValue>) -> Result<DatabaseValue> + Send + Sync>;
+
+#[derive(Clone)]
+struct RegisteredFunction {
+    name: String,
+    arity: i32,
+    func: ScalarFn,
+}
+
+/// SQLite连接池
+///
+/// 与MSSQL不同，这里连接的“网络地址”其实是本地文件路径，因此
+/// `create_connection` 打开的是同一个数据库文件的多个独立连接。
+pub struct SQLitePool {
+    config: crate::core::types::DatabaseConfig,
+    sqlite_config: SQLiteConfig,
+    connections: Arc<RwLock<VecDeque<SQLiteConnection>>>,
+    semaphore: Arc<Semaphore>,
+    total_connections: Arc<RwLock<u32>>,
+    registered_functions: Arc<RwLock<Vec<RegisteredFunction>>>,
+    loaded_extensions: Arc<RwLock<Vec<String>>>,
+    created_at: Instant,
+}
+
+impl SQLitePool {
+    /// 创建新的SQLite连接池
+    pub async fn new(config: &crate::core::types::DatabaseConfig) -> Result<Self> {
+        Self::validate_config(config)?;
+        let sqlite_config = SQLiteConfig::from_database_config(config)?;
+
+        let pool = Self {
+            config: config.clone(),
+            sqlite_config,
+            connections: Arc::new(RwLock::new(VecDeque::new())),
+            semaphore: Arc::new(Semaphore::new(config.pool_config.max_connections as usize)),
+            total_connections: Arc::new(RwLock::new(0)),
+            registered_functions: Arc::new(RwLock::new(Vec::new())),
+            loaded_extensions: Arc::new(RwLock::new(Vec::new())),
+            created_at: Instant::now(),
+        };
+
+        pool.ensure_min_connections().await?;
+        Ok(pool)
+    }
+
+    /// 验证配置
+    pub fn validate_config(config: &crate::core::types::DatabaseConfig) -> Result<()> {
+        if config.db_type != DatabaseType::SQLite {
+            return Err(DbPoolError::Runtime("配置类型不是SQLite".to_string()));
+        }
+
+        if config.database.is_empty() {
+            return Err(DbPoolError::Runtime("SQLite数据库文件路径不能为空".to_string()));
+        }
+
+        Ok(())
+    }
+
+    async fn ensure_min_connections(&self) -> Result<()> {
+        let current_count = {
+            let connections = self.connections.read().await;
+            connections.len() as u32
+        };
+
+        let min_connections = self.config.pool_config.min_connections;
+        if current_count < min_connections {
+            let needed = min_connections - current_count;
+            for _ in 0..needed {
+                let connection = self.create_connection().await?;
+                {
+                    let mut connections = self.connections.write().await;
+                    connections.push_back(connection);
+                }
+                {
+                    let mut total = self.total_connections.write().await;
+                    *total += 1;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 创建新连接，并重放此前注册过的标量函数与已加载的扩展
+    async fn create_connection(&self) -> Result<SQLiteConnection> {
+        let path = self.sqlite_config.path.clone();
+        let functions = self.registered_functions.read().await.clone();
+        let extensions = self.loaded_extensions.read().await.clone();
+        let allow_load_extension = self.sqlite_config.allow_load_extension;
+        let prepared_cache_size = self.config.prepared_cache_size;
+
+        let conn = tokio::task::spawn_blocking(move || -> Result<Connection> {
+            let conn = Connection::open(&path)
+                .map_err(|e| ConnectionError::ConnectionFailed(e.to_string()))?;
+            conn.set_prepared_statement_cache_capacity(prepared_cache_size);
+
+            for registered in &functions {
+                Self::apply_function(&conn, registered)?;
+            }
+
+            if !extensions.is_empty() {
+                if !allow_load_extension {
+                    return Err(DbPoolError::Runtime(
+                        "检测到待加载扩展但配置未开启allow_load_extension".to_string(),
+                    ));
+                }
+                for path in &extensions {
+                    Self::apply_extension(&conn, path)?;
+                }
+            }
+
+            Ok(conn)
+        })
+        .await
+        .map_err(|e| ConnectionError::ConnectionFailed(e.to_string()))??;
+
+        Ok(SQLiteConnection::new(conn))
+    }
+
+    async fn get_connection(&self) -> Result<SQLiteConnection> {
+        let _permit = tokio::time::timeout(
+            self.config.pool_config.acquire_timeout,
+            self.semaphore.acquire(),
+        )
+        .await
+        .map_err(|_| ConnectionError::AcquireTimeout)?
+        .map_err(|_| ConnectionError::PoolExhausted)?;
+
+        {
+            let mut connections = self.connections.write().await;
+            if let Some(mut connection) = connections.pop_front() {
+                if connection.is_valid().await {
+                    return Ok(connection);
+                }
+            }
+        }
+
+        let total_connections = {
+            let guard = self.total_connections.read().await;
+            *guard
+        };
+
+        if total_connections < self.config.pool_config.max_connections {
+            let connection = self.create_connection().await?;
+            {
+                let mut total = self.total_connections.write().await;
+                *total += 1;
+            }
+            Ok(connection)
+        } else {
+            Err(ConnectionError::PoolExhausted.into())
+        }
+    }
+
+    async fn return_connection(&self, mut connection: SQLiteConnection) {
+        if connection.is_valid().await {
+            let mut connections = self.connections.write().await;
+            connections.push_back(connection);
+        } else {
+            let mut total = self.total_connections.write().await;
+            if *total > 0 {
+                *total -= 1;
+            }
+        }
+    }
+
+    /// 在单个裸连接上注册一个标量函数
+    fn apply_function(conn: &Connection, registered: &RegisteredFunction) -> Result<()> {
+        let func = Arc::clone(&registered.func);
+        let n_arg = if registered.arity < 0 { -1 } else { registered.arity };
+
+        conn.create_scalar_function(
+            &registered.name,
+            n_arg,
+            rusqlite::functions::FunctionFlags::SQLITE_UTF8,
+            move |ctx| {
+                let args: Vec<DatabaseValue> = (0..ctx.len())
+                    .map(|i| SQLiteRow::value_from_ref(ctx.get_raw(i)))
+                    .collect();
+
+                let result = func(args).map_err(|e| {
+                    rusqlite::Error::UserFunctionError(Box::new(std::io::Error::new(
+                        std::io::ErrorKind::Other,
+                        e.to_string(),
+                    )))
+                })?;
+
+                Ok(SQLiteTypeConverter::database_value_to_rusqlite(result))
+            },
+        )
+        .map_err(|e| DbPoolError::Runtime(format!("注册标量函数失败: {}", e)))
+    }
+
+    /// 在单个裸连接上加载扩展（调用方需确保已开启`allow_load_extension`）
+    fn apply_extension(conn: &Connection, path: &str) -> Result<()> {
+        unsafe {
+            conn.load_extension_enable()
+                .map_err(|e| DbPoolError::Runtime(format!("开启扩展加载失败: {}", e)))?;
+            let result = conn.load_extension(path, None);
+            let _ = conn.load_extension_disable();
+            result.map_err(|e| DbPoolError::Runtime(format!("加载扩展失败: {}", e)))?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait::async_trait]
+impl DatabasePool for SQLitePool {
+    async fn execute_query(&self, sql: &str, params: Option<QueryParams>) -> Result<polars::frame::DataFrame> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query(sql, params).await;
+        self.return_connection(connection).await;
+
+        match result {
+            Ok(rows) => SQLiteTypeConverter::rows_to_dataframe(rows),
+            Err(e) => Err(e),
+        }
+    }
+
+    async fn execute_query_rows(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+    ) -> Result<Vec<Box<dyn DatabaseRow>>> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.query(sql, params).await;
+        self.return_connection(connection).await;
+
+        result.map(|rows| {
+            rows.into_iter()
+                .map(|row| Box::new(row) as Box<dyn DatabaseRow>)
+                .collect()
+        })
+    }
+
+    /// 真正的惰性游标实现：借`DatabaseConnection::query_stream`逐行拉取，每凑够
+    /// `batch_size`行就转换成一个`DataFrame`批次发出，不会先把整个结果集
+    /// 物化在内存里
+    async fn execute_query_stream(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+        batch_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<polars::frame::DataFrame>>> {
+        let batch_size = batch_size.max(1);
+        let mut connection = self.get_connection().await?;
+        let mut row_rx = connection.query_stream(sql, params, batch_size * 2).await?;
+        // `query_stream`已经取走了底层`conn`，这里只是让`return_connection`
+        // 观察到`is_valid()==false`从而正确扣减`total_connections`，而不是把
+        // 一个已被取走连接的句柄放回可复用队列
+        self.return_connection(connection).await;
+
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+        tokio::spawn(async move {
+            let mut buffer = Vec::with_capacity(batch_size);
+            while let Some(row_result) = row_rx.recv().await {
+                match row_result {
+                    Ok(row) => {
+                        buffer.push(row);
+                        if buffer.len() >= batch_size {
+                            let chunk = std::mem::take(&mut buffer);
+                            let batch = SQLiteTypeConverter::rows_to_dataframe(chunk);
+                            if tx.send(batch).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        let _ = tx.send(Err(e)).await;
+                        return;
+                    }
+                }
+            }
+            if !buffer.is_empty() {
+                let batch = SQLiteTypeConverter::rows_to_dataframe(buffer);
+                let _ = tx.send(batch).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
+    async fn execute_non_query(&self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.execute(sql, params).await;
+        self.return_connection(connection).await;
+        result
+    }
+
+    async fn execute_batch(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>> {
+        let mut connection = self.get_connection().await?;
+        let mut results = Vec::new();
+
+        for operation in operations {
+            let start_time = Instant::now();
+            let result = connection.execute(&operation.sql, operation.params).await;
+            let execution_time = start_time.elapsed();
+
+            match result {
+                Ok(affected_rows) => results.push(BatchResult {
+                    affected_rows,
+                    execution_time,
+                    error: None,
+                }),
+                Err(e) => results.push(BatchResult {
+                    affected_rows: 0,
+                    execution_time,
+                    error: Some(e.to_string()),
+                }),
+            }
+        }
+
+        self.return_connection(connection).await;
+        Ok(results)
+    }
+
+    async fn execute_transaction(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>> {
+        let mut connection = self.get_connection().await?;
+        let mut results = Vec::new();
+
+        connection.begin_transaction().await?;
+        let mut transaction_failed = false;
+
+        for operation in operations {
+            let start_time = Instant::now();
+            let result = connection.execute(&operation.sql, operation.params).await;
+            let execution_time = start_time.elapsed();
+
+            match result {
+                Ok(affected_rows) => results.push(BatchResult {
+                    affected_rows,
+                    execution_time,
+                    error: None,
+                }),
+                Err(e) => {
+                    results.push(BatchResult {
+                        affected_rows: 0,
+                        execution_time,
+                        error: Some(e.to_string()),
+                    });
+                    transaction_failed = true;
+                    break;
+                }
+            }
+        }
+
+        if transaction_failed {
+            connection.rollback_transaction().await?;
+        } else {
+            connection.commit_transaction().await?;
+        }
+
+        self.return_connection(connection).await;
+        Ok(results)
+    }
+
+    /// 预热连接的语句缓存（rusqlite按SQL文本维护的每连接LRU缓存）并记录结果列名，
+    /// 使后续 `execute_prepared` 不再需要重新规划执行计划
+    async fn prepare(&self, sql: &str) -> Result<StatementHandle> {
+        let mut connection = self.get_connection().await?;
+        let result = connection.describe_statement(sql).await;
+        self.return_connection(connection).await;
+
+        let column_names = result?;
+        Ok(StatementHandle {
+            sql: sql.to_string(),
+            param_type_oids: Vec::new(),
+            column_type_oids: Vec::new(),
+            column_names,
+        })
+    }
+
+    async fn execute_prepared(
+        &self,
+        handle: &StatementHandle,
+        params: Option<QueryParams>,
+    ) -> Result<polars::frame::DataFrame> {
+        // rusqlite的连接级语句缓存已经在 `prepare` 时预热过，这里直接按SQL文本
+        // 执行即可命中缓存，无需再借助 `handle` 里的列信息
+        self.execute_query(&handle.sql, params).await
+    }
+
+    async fn begin_session(&self) -> Result<Box<dyn TransactionSession>> {
+        let mut connection = self.get_connection().await?;
+        connection.begin_transaction().await?;
+
+        Ok(Box::new(SQLiteTransactionSession {
+            connection: Some(connection),
+            total_connections: Arc::clone(&self.total_connections),
+            savepoint_counter: 0,
+        }))
+    }
+
+    /// 在线增量备份：每步复制 `pages_per_step` 页并检查进度，直至完成
+    ///
+    /// 基于SQLite的Online Backup API（`sqlite3_backup_init/step/finish`），
+    /// 源数据库在备份过程中仍可被正常读写。
+    async fn backup_to(&self, dest_path: &str, pages_per_step: i32) -> Result<()> {
+        let mut connection = self.get_connection().await?;
+        let conn = connection.take_conn()?;
+        let dest_path = dest_path.to_string();
+        let pages = if pages_per_step <= 0 { -1 } else { pages_per_step };
+
+        let (conn, result) = tokio::task::spawn_blocking(move || {
+            let result: Result<()> = (|| {
+                let mut dest = Connection::open(&dest_path)
+                    .map_err(|e| DbPoolError::Runtime(format!("无法打开备份目标: {}", e)))?;
+                let backup = rusqlite::backup::Backup::new(&conn, &mut dest)
+                    .map_err(|e| DbPoolError::Runtime(format!("创建备份会话失败: {}", e)))?;
+
+                loop {
+                    let progress = backup
+                        .step(pages)
+                        .map_err(|e| DbPoolError::Runtime(format!("备份步骤失败: {}", e)))?;
+                    if progress == rusqlite::backup::StepResult::Done {
+                        break;
+                    }
+                }
+                Ok(())
+            })();
+            (conn, result)
+        })
+        .await
+        .map_err(|e| DbPoolError::Runtime(e.to_string()))?;
+
+        connection.restore_conn(conn);
+        self.return_connection(connection).await;
+        result
+    }
+
+    /// 注册标量UDF
+    ///
+    /// 简化实现：新函数会立即应用到本次借出的连接，并记录下来供此后
+    /// `create_connection` 新建的连接自动携带；但池中其它当下空闲的
+    /// 连接不会被回溯更新，直至它们因失效被重建。对依赖该函数的场景，
+    /// 建议在建池后、发起查询前调用本方法，或配合 `min_connections=1`。
+    async fn register_scalar_function(
+        &self,
+        name: &str,
+        arity: i32,
+        func: ScalarFn,
+    ) -> Result<()> {
+        let registered = RegisteredFunction {
+            name: name.to_string(),
+            arity,
+            func,
+        };
+
+        let mut connection = self.get_connection().await?;
+        let conn = connection.take_conn()?;
+        let to_apply = registered.clone();
+
+        let (conn, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::apply_function(&conn, &to_apply);
+            (conn, result)
+        })
+        .await
+        .map_err(|e| DbPoolError::Runtime(e.to_string()))?;
+
+        connection.restore_conn(conn);
+        self.return_connection(connection).await;
+        result?;
+
+        self.registered_functions.write().await.push(registered);
+        Ok(())
+    }
+
+    /// 加载数据库扩展，需要配置中显式开启 `allow_load_extension`
+    async fn load_extension(&self, path: &str) -> Result<()> {
+        if !self.sqlite_config.allow_load_extension {
+            return Err(DbPoolError::Runtime(
+                "未开启load_extension，需要在创建连接池时设置allow_load_extension=true".to_string(),
+            ));
+        }
+
+        let mut connection = self.get_connection().await?;
+        let conn = connection.take_conn()?;
+        let path_owned = path.to_string();
+
+        let (conn, result) = tokio::task::spawn_blocking(move || {
+            let result = Self::apply_extension(&conn, &path_owned);
+            (conn, result)
+        })
+        .await
+        .map_err(|e| DbPoolError::Runtime(e.to_string()))?;
+
+        connection.restore_conn(conn);
+        self.return_connection(connection).await;
+        result?;
+
+        self.loaded_extensions.write().await.push(path.to_string());
+        Ok(())
+    }
+
+    async fn get_status(&self) -> Result<PoolStatus> {
+        let connections = self.connections.read().await;
+        let total = self.total_connections.read().await;
+        let idle = connections.len() as u32;
+
+        Ok(PoolStatus {
+            pool_id: String::new(),
+            db_type: DatabaseType::SQLite,
+            total_connections: *total,
+            active_connections: total.saturating_sub(idle),
+            idle_connections: idle,
+            waiting_connections: 0,
+            is_healthy: true,
+            last_error: None,
+            uptime: self.created_at.elapsed(),
+            circuit_state: crate::core::types::CircuitState::Closed, // 由HealthMonitor覆盖
+            consecutive_failures: 0,
+        })
+    }
+
+    async fn health_check(&self) -> Result<bool> {
+        let mut connection = self.get_connection().await?;
+        let valid = connection.is_valid().await;
+        self.return_connection(connection).await;
+        Ok(valid)
+    }
+
+    async fn close(&self) -> Result<()> {
+        let mut connections = self.connections.write().await;
+        while let Some(mut connection) = connections.pop_front() {
+            let _ = connection.close().await;
+        }
+
+        let mut total = self.total_connections.write().await;
+        *total = 0;
+
+        Ok(())
+    }
+}
+
+/// 从 `SQLitePool` 租用的事务会话
+///
+/// 与 `MSSQLTransactionSession` 同样的简化：结束后直接关闭连接而非归还池，
+/// 避免引入与 `SQLitePool` 的共享所有权生命周期管理。
+struct SQLiteTransactionSession {
+    connection: Option<SQLiteConnection>,
+    total_connections: Arc<RwLock<u32>>,
+    savepoint_counter: u32,
+}
+
+impl SQLiteTransactionSession {
+    fn connection_mut(&mut self) -> Result<&mut SQLiteConnection> {
+        self.connection
+            .as_mut()
+            .ok_or_else(|| DbPoolError::Runtime("事务会话已结束".to_string()))
+    }
+
+    async fn finish(&mut self) {
+        if let Some(mut connection) = self.connection.take() {
+            let _ = connection.close().await;
+            let mut total = self.total_connections.write().await;
+            if *total > 0 {
+                *total -= 1;
+            }
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl TransactionSession for SQLiteTransactionSession {
+    async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<polars::frame::DataFrame> {
+        let rows = self.connection_mut()?.query(sql, params).await?;
+        SQLiteTypeConverter::rows_to_dataframe(rows)
+    }
+
+    async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64> {
+        self.connection_mut()?.execute(sql, params).await
+    }
+
+    async fn commit(&mut self) -> Result<()> {
+        self.connection_mut()?.commit_transaction().await?;
+        self.finish().await;
+        Ok(())
+    }
+
+    async fn rollback(&mut self) -> Result<()> {
+        self.connection_mut()?.rollback_transaction().await?;
+        self.finish().await;
+        Ok(())
+    }
+
+    async fn savepoint(&mut self, name: &str) -> Result<()> {
+        self.savepoint_counter += 1;
+        self.connection_mut()?
+            .execute(&format!("SAVEPOINT {}", name), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn release_savepoint(&mut self, name: &str) -> Result<()> {
+        self.connection_mut()?
+            .execute(&format!("RELEASE SAVEPOINT {}", name), None)
+            .await?;
+        Ok(())
+    }
+
+    async fn rollback_to(&mut self, name: &str) -> Result<()> {
+        self.connection_mut()?
+            .execute(&format!("ROLLBACK TO SAVEPOINT {}", name), None)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod execute_query_stream_tests {
+    use super::*;
+    use crate::core::types::{DatabaseConfig, PoolConfig, TimeoutConfig};
+
+    fn test_config() -> DatabaseConfig {
+        DatabaseConfig {
+            db_type: DatabaseType::SQLite,
+            host: String::new(),
+            port: 0,
+            database: ":memory:".to_string(),
+            username: String::new(),
+            password: String::new(),
+            pool_config: PoolConfig {
+                min_connections: 1,
+                max_connections: 1,
+                acquire_timeout: Duration::from_secs(5),
+                idle_timeout: Duration::from_secs(300),
+                max_lifetime: Duration::from_secs(1800),
+                auto_scaling: false,
+                scale_up_threshold: 0.8,
+                scale_down_threshold: 0.3,
+                health_check_interval: Duration::from_secs(30),
+            },
+            ssl_config: None,
+            timeout_config: TimeoutConfig::default(),
+            application_name: None,
+            allow_load_extension: false,
+            prepared_cache_size: 16,
+        }
+    }
+
+    async fn seeded_pool(row_count: u32) -> SQLitePool {
+        let pool = SQLitePool::new(&test_config()).await.unwrap();
+        pool.execute_non_query("CREATE TABLE nums (n INTEGER NOT NULL)", None)
+            .await
+            .unwrap();
+        for n in 0..row_count {
+            pool.execute_non_query(
+                &format!("INSERT INTO nums (n) VALUES ({})", n),
+                None,
+            )
+            .await
+            .unwrap();
+        }
+        pool
+    }
+
+    /// 若`execute_query_stream`退化成「先`execute_query`整体物化再切片」，拿到
+    /// 第一批的耗时会随结果集总行数线性增长；真正的惰性游标实现中，第一批
+    /// 耗时只取决于`batch_size`，与结果集总行数无关。用一个行数相差100倍的
+    /// 小表和大表分别计时，断言首批耗时的比值远小于行数的比值，证明结果集
+    /// 没有在发出第一批之前被整体读入内存。
+    #[tokio::test]
+    async fn first_batch_latency_does_not_scale_with_result_size() {
+        let batch_size = 20;
+
+        let small_pool = seeded_pool(200).await;
+        let small_start = Instant::now();
+        let mut small_rx = small_pool
+            .execute_query_stream("SELECT n FROM nums ORDER BY n", None, batch_size)
+            .await
+            .unwrap();
+        let small_first_batch = small_rx.recv().await.unwrap().unwrap();
+        let small_elapsed = small_start.elapsed();
+        assert_eq!(small_first_batch.height(), batch_size);
+
+        let large_pool = seeded_pool(20_000).await;
+        let large_start = Instant::now();
+        let mut large_rx = large_pool
+            .execute_query_stream("SELECT n FROM nums ORDER BY n", None, batch_size)
+            .await
+            .unwrap();
+        let large_first_batch = large_rx.recv().await.unwrap().unwrap();
+        let large_elapsed = large_start.elapsed();
+        assert_eq!(large_first_batch.height(), batch_size);
+
+        // 结果集行数相差100倍，但首批到达耗时的比值应当远小于10倍；
+        // 给足够的容忍度，避免测试机抖动导致误报
+        assert!(
+            large_elapsed.as_secs_f64() < small_elapsed.as_secs_f64() * 10.0 + 0.05,
+            "first batch took {:?} for 20000 rows vs {:?} for 200 rows, looks like the full result is materialized before streaming",
+            large_elapsed,
+            small_elapsed,
+        );
+    }
+
+    #[tokio::test]
+    async fn streams_all_rows_across_multiple_batches() {
+        let pool = seeded_pool(105).await;
+        let mut rx = pool
+            .execute_query_stream("SELECT n FROM nums ORDER BY n", None, 50)
+            .await
+            .unwrap();
+
+        let mut total_rows = 0usize;
+        let mut batch_count = 0usize;
+        while let Some(batch) = rx.recv().await {
+            total_rows += batch.unwrap().height();
+            batch_count += 1;
+        }
+
+        assert_eq!(total_rows, 105);
+        assert_eq!(batch_count, 3);
+    }
+}
@@ -0,0 +1,297 @@
+use crate::core::error::{ConversionError, Result};
+use crate::core::types::DatabaseValue;
+use crate::databases::traits::{DatabaseRow, TypeConverter};
+use polars::prelude::*;
+use rusqlite::types::{ValueRef, Value};
+use std::collections::HashMap;
+
+/// SQLite行数据
+///
+/// `rusqlite::Row` 借用自其所在的 `Statement`，无法跨越 `spawn_blocking`
+/// 的闭包边界存活，因此这里在拿到行的那一刻就把每一列物化为
+/// `DatabaseValue`，而不是像 `MSSQLRow` 那样持有原始驱动行对象。
+pub struct SQLiteRow {
+    column_names: Vec<String>,
+    values: Vec<DatabaseValue>,
+}
+
+impl SQLiteRow {
+    pub fn from_row(row: &rusqlite::Row, column_names: &[String]) -> rusqlite::Result<Self> {
+        let mut values = Vec::with_capacity(column_names.len());
+        for i in 0..column_names.len() {
+            values.push(Self::value_from_ref(row.get_ref(i)?));
+        }
+
+        Ok(Self {
+            column_names: column_names.to_vec(),
+            values,
+        })
+    }
+
+    /// 将rusqlite的借用值转换为 `DatabaseValue`，供行物化与标量函数参数共用
+    pub(crate) fn value_from_ref(value_ref: ValueRef) -> DatabaseValue {
+        match value_ref {
+            ValueRef::Null => DatabaseValue::Null,
+            ValueRef::Integer(i) => DatabaseValue::I64(i),
+            ValueRef::Real(f) => DatabaseValue::F64(f),
+            ValueRef::Text(t) => DatabaseValue::String(String::from_utf8_lossy(t).to_string()),
+            ValueRef::Blob(b) => DatabaseValue::Bytes(b.to_vec()),
+        }
+    }
+}
+
+impl DatabaseRow for SQLiteRow {
+    fn column_count(&self) -> usize {
+        self.column_names.len()
+    }
+
+    fn column_names(&self) -> Vec<String> {
+        self.column_names.clone()
+    }
+
+    fn get_value(&self, index: usize) -> Option<DatabaseValue> {
+        self.values.get(index).cloned()
+    }
+
+    fn get_value_by_name(&self, name: &str) -> Option<DatabaseValue> {
+        let index = self.column_names.iter().position(|n| n == name)?;
+        self.get_value(index)
+    }
+
+    fn to_map(&self) -> HashMap<String, DatabaseValue> {
+        self.column_names
+            .iter()
+            .cloned()
+            .zip(self.values.iter().cloned())
+            .collect()
+    }
+}
+
+/// SQLite类型转换器
+pub struct SQLiteTypeConverter;
+
+impl TypeConverter for SQLiteTypeConverter {
+    fn rows_to_dataframe<R: DatabaseRow>(rows: Vec<R>) -> Result<DataFrame> {
+        if rows.is_empty() {
+            return Ok(DataFrame::empty());
+        }
+
+        let column_names = rows[0].column_names();
+        let column_count = column_names.len();
+
+        let mut columns: Vec<Vec<AnyValue>> = vec![Vec::new(); column_count];
+
+        for row in &rows {
+            for (col_idx, _) in column_names.iter().enumerate() {
+                if let Some(value) = row.get_value(col_idx) {
+                    columns[col_idx].push(Self::database_value_to_any_value(value));
+                } else {
+                    columns[col_idx].push(AnyValue::Null);
+                }
+            }
+        }
+
+        let mut df_columns = Vec::new();
+        for (i, col_name) in column_names.iter().enumerate() {
+            let series = Self::create_series_from_values(col_name, &columns[i])?;
+            df_columns.push(series);
+        }
+
+        DataFrame::new(df_columns)
+            .map_err(|e| ConversionError::DataFrameConversion(e.to_string()).into())
+    }
+
+    fn database_value_to_any_value(value: DatabaseValue) -> AnyValue<'static> {
+        match value {
+            DatabaseValue::Null => AnyValue::Null,
+            DatabaseValue::Bool(b) => AnyValue::Boolean(b),
+            DatabaseValue::I32(i) => AnyValue::Int32(i),
+            DatabaseValue::I64(i) => AnyValue::Int64(i),
+            DatabaseValue::F32(f) => AnyValue::Float32(f),
+            DatabaseValue::F64(f) => AnyValue::Float64(f),
+            DatabaseValue::String(s) => AnyValue::StringOwned(s.into()),
+            DatabaseValue::Bytes(b) => AnyValue::BinaryOwned(b),
+            DatabaseValue::DateTime(dt) => {
+                AnyValue::Datetime(dt.timestamp_millis(), TimeUnit::Milliseconds, &None)
+            }
+            DatabaseValue::Uuid(u) => AnyValue::StringOwned(u.to_string().into()),
+            // SQLite后端从不产出这几种值（`get_value`只识别Null/Integer/Real/Text/Blob），
+            // 这里只需应对标量函数回传或跨后端转发的情况，按文本落地
+            DatabaseValue::Decimal(d) => AnyValue::StringOwned(d.to_string().into()),
+            DatabaseValue::Date(d) => AnyValue::StringOwned(d.to_string().into()),
+            DatabaseValue::Time(t) => AnyValue::StringOwned(t.to_string().into()),
+            DatabaseValue::DateTimeTz(dt) => AnyValue::StringOwned(dt.to_rfc3339().into()),
+        }
+    }
+
+    fn convert_params(params: &crate::core::types::QueryParams) -> Result<Vec<(String, DatabaseValue)>> {
+        Ok(params.iter().map(|(k, v)| (k.clone(), v.clone())).collect())
+    }
+}
+
+impl SQLiteTypeConverter {
+    /// 将标量函数的返回值转换为rusqlite可以直接写回结果集的 `Value`
+    pub(crate) fn database_value_to_rusqlite(value: DatabaseValue) -> Value {
+        match value {
+            DatabaseValue::Null => Value::Null,
+            DatabaseValue::Bool(b) => Value::Integer(b as i64),
+            DatabaseValue::I32(i) => Value::Integer(i as i64),
+            DatabaseValue::I64(i) => Value::Integer(i),
+            DatabaseValue::F32(f) => Value::Real(f as f64),
+            DatabaseValue::F64(f) => Value::Real(f),
+            DatabaseValue::String(s) => Value::Text(s),
+            DatabaseValue::Bytes(b) => Value::Blob(b),
+            DatabaseValue::DateTime(dt) => Value::Integer(dt.timestamp_millis()),
+            DatabaseValue::Uuid(u) => Value::Text(u.to_string()),
+            DatabaseValue::Decimal(d) => Value::Text(d.to_string()),
+            DatabaseValue::Date(d) => Value::Text(d.to_string()),
+            DatabaseValue::Time(t) => Value::Text(t.to_string()),
+            DatabaseValue::DateTimeTz(dt) => Value::Text(dt.to_rfc3339()),
+        }
+    }
+
+    fn create_series_from_values(name: &str, values: &[AnyValue]) -> Result<Series> {
+        if values.is_empty() {
+            return Ok(Series::new_empty(name, &DataType::Null));
+        }
+
+        // 对整列做两遍扫描：先宽化出能容纳所有值的最窄类型，再按该类型统一
+        // 取值，而不是只取第一个非空值的类型——SQLite是动态类型，同一列完全
+        // 可能INTEGER/REAL/TEXT混杂，只看第一个值会把后面不同变体的值静默
+        // 转成None，丢数据且不报错
+        let data_type = Self::infer_data_type(values);
+
+        match data_type {
+            DataType::Boolean => {
+                let bool_values: Vec<Option<bool>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Boolean(b) => Some(*b),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, bool_values))
+            }
+            DataType::Int64 => {
+                let int_values: Vec<Option<i64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Int64(i) => Some(*i),
+                        AnyValue::Int32(i) => Some(*i as i64),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, int_values))
+            }
+            DataType::Float64 => {
+                let float_values: Vec<Option<f64>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Float64(f) => Some(*f),
+                        AnyValue::Float32(f) => Some(*f as f64),
+                        AnyValue::Int64(i) => Some(*i as f64),
+                        AnyValue::Int32(i) => Some(*i as f64),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, float_values))
+            }
+            DataType::String => {
+                let string_values: Vec<Option<String>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::String(s) => Some(s.to_string()),
+                        AnyValue::StringOwned(s) => Some(s.to_string()),
+                        AnyValue::Null => None,
+                        _ => Some(format!("{:?}", v)),
+                    })
+                    .collect();
+                Ok(Series::new(name, string_values))
+            }
+            DataType::Binary => {
+                let binary_values: Vec<Option<Vec<u8>>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Binary(b) => Some(b.to_vec()),
+                        AnyValue::BinaryOwned(b) => Some(b.clone()),
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, binary_values))
+            }
+            _ => {
+                let string_values: Vec<Option<String>> = values
+                    .iter()
+                    .map(|v| match v {
+                        AnyValue::Null => None,
+                        _ => Some(format!("{:?}", v)),
+                    })
+                    .collect();
+                Ok(Series::new(name, string_values))
+            }
+        }
+    }
+
+    /// 对整列做两遍扫描，解析出能容纳所有非空值的最窄类型
+    ///
+    /// SQLite采用动态类型，一列中的值可能整数/浮点数/文本混杂；只看第一个
+    /// 非空值的类型会让后续不同变体的值在`create_series_from_values`里静默
+    /// 丢失（落为None）。按`Null → Boolean/Int64/Float64/Binary → String`的
+    /// 格做宽化：数值型之间（Int64/Float64）提升到Float64，其余任何冲突组合
+    /// 都没有公共上界，统一退回String。
+    fn infer_data_type(values: &[AnyValue]) -> DataType {
+        let mut dtype = DataType::Null;
+        for value in values {
+            let incoming = match value {
+                AnyValue::Null => continue,
+                AnyValue::Boolean(_) => DataType::Boolean,
+                AnyValue::Int32(_) | AnyValue::Int64(_) => DataType::Int64,
+                AnyValue::Float32(_) | AnyValue::Float64(_) => DataType::Float64,
+                AnyValue::String(_) | AnyValue::StringOwned(_) => DataType::String,
+                AnyValue::Binary(_) | AnyValue::BinaryOwned(_) => DataType::Binary,
+                _ => DataType::String,
+            };
+            dtype = Self::widen_dtype(dtype, incoming);
+        }
+        dtype
+    }
+
+    /// 类型格的最小上界，规则同`crate::utils::dataframe::DataFrameConverter::widen_dtype`：
+    /// `Null`吸收任何类型，`Int64`/`Float64`互相提升到`Float64`，其余任何不
+    /// 相同的组合都没有公共上界，退回`String`
+    fn widen_dtype(current: DataType, incoming: DataType) -> DataType {
+        use DataType::*;
+        match (current, incoming) {
+            (Null, t) | (t, Null) => t,
+            (Boolean, Boolean) => Boolean,
+            (Int64, Int64) => Int64,
+            (Float64, Float64) => Float64,
+            (Int64, Float64) | (Float64, Int64) => Float64,
+            (Binary, Binary) => Binary,
+            (String, String) => String,
+            _ => String,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widens_mixed_integer_and_float_column_instead_of_dropping_values() {
+        let values = vec![AnyValue::Int64(1), AnyValue::Float64(2.5), AnyValue::Int64(3)];
+        let series = SQLiteTypeConverter::create_series_from_values("col", &values).unwrap();
+        assert_eq!(series.dtype(), &DataType::Float64);
+        let floats: Vec<Option<f64>> = series.f64().unwrap().into_iter().collect();
+        assert_eq!(floats, vec![Some(1.0), Some(2.5), Some(3.0)]);
+    }
+
+    #[test]
+    fn widens_mixed_numeric_and_text_column_to_string() {
+        let values = vec![AnyValue::Int64(1), AnyValue::StringOwned("two".into())];
+        let series = SQLiteTypeConverter::create_series_from_values("col", &values).unwrap();
+        assert_eq!(series.dtype(), &DataType::String);
+        assert_eq!(series.len(), 2);
+    }
+}
@@ -1,8 +1,34 @@
-use crate::core::error::Result;
-use crate::core::types::{QueryParams, BatchOperation, BatchResult, PoolStatus, DatabaseValue};
+use crate::core::error::{ConfigError, ConversionError, DbPoolError, Result};
+use crate::core::types::{QueryParams, BatchOperation, BatchResult, PoolStatus, DatabaseValue, ColumnSchema, ResultFormat};
 use async_trait::async_trait;
 use polars::frame::DataFrame;
+use polars::series::Series;
 use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 一条已准备语句的后端句柄：持有SQL文本及（若后端已推断出）参数/结果列的类型元数据
+///
+/// 与 `crate::core::prepared::PreparedStatementHandle`（面向调用方、可重复执行的
+/// 句柄）不同，这是 `DatabasePool` 内部bind/execute分离的扩展查询接口所用的
+/// 轻量句柄，由各后端自行决定是否真正复用底层已编译语句。
+#[derive(Debug, Clone)]
+pub struct StatementHandle {
+    pub sql: String,
+    pub param_type_oids: Vec<i32>,
+    pub column_type_oids: Vec<i32>,
+    pub column_names: Vec<String>,
+}
+
+impl StatementHandle {
+    pub fn new(sql: impl Into<String>) -> Self {
+        Self {
+            sql: sql.into(),
+            param_type_oids: Vec::new(),
+            column_type_oids: Vec::new(),
+            column_names: Vec::new(),
+        }
+    }
+}
 
 /// 数据库连接池特征
 #[async_trait]
@@ -10,6 +36,16 @@ pub trait DatabasePool: Send + Sync {
     /// 执行查询并返回DataFrame
     async fn execute_query(&self, sql: &str, params: Option<QueryParams>) -> Result<DataFrame>;
 
+    /// 执行查询并以装箱的 `DatabaseRow` 返回原始行，不物化DataFrame
+    ///
+    /// 供 `query_as` 转换为调用方的具体类型；相比 `execute_query`，跳过了
+    /// Polars `Series` 的构建与类型推断，适合只想要 `Vec<T>` 的Rust调用方。
+    async fn execute_query_rows(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+    ) -> Result<Vec<Box<dyn DatabaseRow>>>;
+
     /// 执行非查询操作（INSERT, UPDATE, DELETE等）
     async fn execute_non_query(&self, sql: &str, params: Option<QueryParams>) -> Result<u64>;
 
@@ -19,6 +55,163 @@ pub trait DatabasePool: Send + Sync {
     /// 执行事务
     async fn execute_transaction(&self, operations: Vec<BatchOperation>) -> Result<Vec<BatchResult>>;
 
+    /// 从池中租用一个连接，开启一个可由调用方手动提交/回滚的事务会话
+    async fn begin_session(&self) -> Result<Box<dyn TransactionSession>>;
+
+    /// 准备一条语句，返回可重复执行的句柄
+    ///
+    /// 默认实现不做任何缓存，仅将SQL文本原样包装；能真正复用底层已编译
+    /// 语句（如SQLite基于rusqlite自带的语句缓存）的后端应重写本方法。
+    async fn prepare(&self, sql: &str) -> Result<StatementHandle> {
+        Ok(StatementHandle::new(sql))
+    }
+
+    /// 执行一条已准备的语句
+    ///
+    /// 默认实现退化为按SQL文本直接查询，不享受缓存收益。
+    async fn execute_prepared(
+        &self,
+        handle: &StatementHandle,
+        params: Option<QueryParams>,
+    ) -> Result<DataFrame> {
+        self.execute_query(&handle.sql, params).await
+    }
+
+    /// 在线增量备份当前数据库到目标路径，每步复制 `pages_per_step` 页
+    ///
+    /// 默认未实现，目前仅SQLite后端（基于其page-level备份API）覆盖此方法。
+    async fn backup_to(&self, _dest_path: &str, _pages_per_step: i32) -> Result<()> {
+        Err(DbPoolError::Runtime("当前后端不支持在线备份".to_string()))
+    }
+
+    /// 注册一个标量UDF，之后执行的查询可直接按名称调用
+    ///
+    /// 默认未实现，目前仅SQLite后端覆盖此方法。
+    async fn register_scalar_function(
+        &self,
+        _name: &str,
+        _arity: i32,
+        _func: Arc<dyn Fn(Vec<DatabaseValue>) -> Result<DatabaseValue> + Send + Sync>,
+    ) -> Result<()> {
+        Err(DbPoolError::Runtime("当前后端不支持注册标量函数".to_string()))
+    }
+
+    /// 加载一个数据库扩展（需要后端在配置中显式开启该能力）
+    ///
+    /// 默认未实现，目前仅SQLite后端覆盖此方法。
+    async fn load_extension(&self, _path: &str) -> Result<()> {
+        Err(DbPoolError::Runtime("当前后端不支持加载扩展".to_string()))
+    }
+
+    /// 将一个Polars `DataFrame`批量写回目标表，反向完成`execute_query`的物化过程
+    ///
+    /// 默认未实现，目前仅MSSQL后端（基于TDS批量插入）覆盖此方法。
+    async fn write_dataframe(
+        &self,
+        _table: &str,
+        _df: &DataFrame,
+        _mode: crate::core::types::WriteMode,
+    ) -> Result<u64> {
+        Err(DbPoolError::Runtime("当前后端不支持DataFrame写回".to_string()))
+    }
+
+    /// 在不拉取任何行的情况下，获取一条查询的结果集列名与推断类型
+    ///
+    /// 默认未实现，目前仅MSSQL后端（基于查询返回的列元数据）覆盖此方法。
+    async fn describe(&self, _sql: &str) -> Result<Vec<ColumnSchema>> {
+        Err(DbPoolError::Runtime("当前后端不支持结构自省".to_string()))
+    }
+
+    /// 执行查询并将每一行按位置转换为 `T`，跳过DataFrame物化
+    ///
+    /// 依赖 `Self: Sized`，因此不会进入trait对象的虚表，仅能在具体类型（或
+    /// 已知具体类型的泛型上下文）上调用；通过 `Arc<dyn DatabasePool>` 持有
+    /// 连接池的调用方应改用 `execute_query_rows` 自行转换。
+    async fn query_as<T>(&self, sql: &str, params: Option<QueryParams>) -> Result<Vec<T>>
+    where
+        Self: Sized,
+        T: crate::core::row::FromRow,
+    {
+        let rows = self.execute_query_rows(sql, params).await?;
+        rows.iter().map(|row| T::from_row(row.as_ref())).collect()
+    }
+
+    /// 按位置绑定参数执行查询，`result_format`选择数值/时间戳等列是按驱动的二进制
+    /// 线缆表示精确解码（`Binary`）还是统一物化为字符串（`Text`，兼容性兜底）
+    ///
+    /// 默认实现把位置参数转换为`p1`..`pn`具名参数，退化到`execute_query`的具名
+    /// 占位符路径，并且忽略`result_format`（始终按各后端既有的列类型分派解码）；
+    /// 真正支持`Text`、并在执行前按驱动推断的占位符类型做校验的是Postgres/MSSQL
+    /// 各自的覆盖实现。
+    async fn query_with_params(
+        &self,
+        sql: &str,
+        params: &[DatabaseValue],
+        _result_format: ResultFormat,
+    ) -> Result<DataFrame> {
+        let named: QueryParams = params
+            .iter()
+            .cloned()
+            .enumerate()
+            .map(|(i, v)| (format!("p{}", i + 1), v))
+            .collect();
+        self.execute_query(sql, Some(named)).await
+    }
+
+    /// 从`table`中按确定性顺序采样最多`n`行：按`hash(pk_column, seed)`排序取前
+    /// `n`行，相同`seed`对同一张表重复调用会命中同一批行；排序与截断都下推到
+    /// 数据库引擎执行，不需要先把整表拉回内存
+    ///
+    /// 默认实现假设后端支持标准SQL的`LIMIT`子句（Postgres/SQLite均如此）；
+    /// MSSQL的T-SQL没有`LIMIT`，由其自身覆盖实现改用`TOP`。
+    async fn sample_table(&self, table: &str, pk_column: &str, n: usize, seed: i64) -> Result<DataFrame> {
+        validate_sql_identifier(table)?;
+        validate_sql_identifier(pk_column)?;
+        let sql = format!(
+            "SELECT * FROM {table} ORDER BY {order} LIMIT {n}",
+            table = table,
+            order = deterministic_sample_order(pk_column, seed),
+            n = n
+        );
+        self.execute_query(&sql, None).await
+    }
+
+    /// 以流式方式执行查询，按`batch_size`行切分结果为多个`DataFrame`，通过
+    /// 有界channel回传；消费者每次`recv().await`才会被再填充一批，由此提供
+    /// 背压，供`DistributedPoolManager::execute_query_stream`复用
+    ///
+    /// 默认实现等价于先完整`execute_query`再整体切片，仍会把结果集留在内存
+    /// 里；各后端应覆盖为基于`DatabaseConnection::query_stream`惰性游标的真正
+    /// 流式实现，避免大结果集撑爆内存（见各自的覆盖实现）
+    async fn execute_query_stream(
+        &self,
+        sql: &str,
+        params: Option<QueryParams>,
+        batch_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<DataFrame>>> {
+        let batch_size = batch_size.max(1);
+        let df = self.execute_query(sql, params).await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(1);
+
+        tokio::spawn(async move {
+            let height = df.height();
+            let mut offset = 0usize;
+            while offset < height {
+                let len = batch_size.min(height - offset);
+                let batch = df.slice(offset as i64, len);
+                if tx.send(Ok(batch)).await.is_err() {
+                    return;
+                }
+                offset += len;
+            }
+            if height == 0 {
+                let _ = tx.send(Ok(df)).await;
+            }
+        });
+
+        Ok(rx)
+    }
+
     /// 获取连接池状态
     async fn get_status(&self) -> Result<PoolStatus>;
 
@@ -29,6 +222,31 @@ pub trait DatabasePool: Send + Sync {
     async fn close(&self) -> Result<()>;
 }
 
+/// 事务会话：持有从池中租用的单个连接，直到 `commit`/`rollback` 结束
+#[async_trait]
+pub trait TransactionSession: Send + Sync {
+    /// 在该事务连接上执行查询
+    async fn query(&mut self, sql: &str, params: Option<QueryParams>) -> Result<DataFrame>;
+
+    /// 在该事务连接上执行非查询操作
+    async fn execute(&mut self, sql: &str, params: Option<QueryParams>) -> Result<u64>;
+
+    /// 提交事务并归还/关闭底层连接
+    async fn commit(&mut self) -> Result<()>;
+
+    /// 回滚事务并归还/关闭底层连接
+    async fn rollback(&mut self) -> Result<()>;
+
+    /// 创建保存点
+    async fn savepoint(&mut self, name: &str) -> Result<()>;
+
+    /// 释放保存点
+    async fn release_savepoint(&mut self, name: &str) -> Result<()>;
+
+    /// 回滚到指定保存点
+    async fn rollback_to(&mut self, name: &str) -> Result<()>;
+}
+
 /// 数据库行特征
 pub trait DatabaseRow: Send + Sync {
     /// 获取列数
@@ -72,6 +290,38 @@ pub trait DatabaseConnection: Send + Sync {
 
     /// 关闭连接
     async fn close(&mut self) -> Result<()>;
+
+    /// 以流式方式读取查询结果：每解出一行就立刻通过有界channel发出，不在
+    /// 内存里攒一份完整的`Vec<Self::Row>`；`buffer_size`是channel容量，即
+    /// 生产者最多能领先消费者缓冲多少行，由此提供背压
+    ///
+    /// 流结束（或消费者中途丢弃`Receiver`）后底层连接即被关闭，不会像
+    /// `query`那样可以继续复用——调用方应把本方法当成一次性消费连接，而不是
+    /// 像其它方法一样执行完再把连接还回连接池
+    ///
+    /// 默认实现等价于先完整`query`再逐行回放，仍然会把整个结果集留在内存
+    /// 里；各后端应在驱动支持惰性游标时覆盖为真正的按行拉取
+    async fn query_stream(
+        &mut self,
+        sql: &str,
+        params: Option<QueryParams>,
+        buffer_size: usize,
+    ) -> Result<tokio::sync::mpsc::Receiver<Result<Self::Row>>>
+    where
+        Self::Row: 'static,
+    {
+        let rows = self.query(sql, params).await?;
+        let (tx, rx) = tokio::sync::mpsc::channel(buffer_size.max(1).max(rows.len()));
+
+        for row in rows {
+            if tx.send(Ok(row)).await.is_err() {
+                break;
+            }
+        }
+
+        let _ = self.close().await;
+        Ok(rx)
+    }
 }
 
 /// 数据库类型转换特征
@@ -86,6 +336,85 @@ pub trait TypeConverter: Send + Sync {
     fn convert_params(params: &QueryParams) -> Result<Vec<(String, DatabaseValue)>>;
 }
 
+/// `DatabasePool::sample_table`的`ORDER BY`表达式：把`pk_column`转成`BIGINT`后
+/// 与`seed`做一次线性同余式散列，比真随机函数更便宜，且同一`seed`在重复调用
+/// 间保证排出同一个顺序
+pub(crate) fn deterministic_sample_order(pk_column: &str, seed: i64) -> String {
+    format!("ABS((CAST({pk_column} AS BIGINT) * 2654435761 + {seed}) % 1000000007)")
+}
+
+/// 校验`sample_table`的`table`/`pk_column`是一个裸标识符（可带`.`做schema限定），
+/// 在拼进SQL文本前拒绝其余任何字符
+///
+/// `table`/`pk_column`来自`TableSampleSpec`，可能最终由配置文件或自省到的表名
+/// 列表填充；`sample_table`的默认实现与MSSQL覆盖实现都把它们直接`format!`进
+/// SQL，不经这层校验就是任意SQL注入
+pub(crate) fn validate_sql_identifier(identifier: &str) -> Result<()> {
+    let is_valid = !identifier.is_empty()
+        && identifier
+            .chars()
+            .next()
+            .map(|c| c.is_ascii_alphabetic() || c == '_')
+            .unwrap_or(false)
+        && identifier
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.');
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(DbPoolError::Config(ConfigError::ValidationFailed(format!(
+            "非法的SQL标识符: {identifier}"
+        ))))
+    }
+}
+
+/// 将任意`DatabaseRow`的全部列统一按文本物化为`DataFrame`：不管原始列类型，
+/// 每个非空值都转换成字符串，供`ResultFormat::Text`使用；与各后端按列类型
+/// 精确解码的`rows_to_dataframe`（`ResultFormat::Binary`）形成对照
+pub fn rows_to_text_dataframe<R: DatabaseRow>(rows: Vec<R>) -> Result<DataFrame> {
+    if rows.is_empty() {
+        return Ok(DataFrame::empty());
+    }
+
+    let column_names = rows[0].column_names();
+    let mut columns: Vec<Vec<Option<String>>> = vec![Vec::new(); column_names.len()];
+
+    for row in &rows {
+        for (col_idx, values) in columns.iter_mut().enumerate() {
+            values.push(row.get_value(col_idx).and_then(|v| database_value_to_text(&v)));
+        }
+    }
+
+    let series: Vec<Series> = column_names
+        .into_iter()
+        .zip(columns)
+        .map(|(name, values)| Series::new(&name, values))
+        .collect();
+
+    DataFrame::new(series).map_err(|e| ConversionError::DataFrameConversion(e.to_string()).into())
+}
+
+/// `DatabaseValue`的文本表示，`Null`映射为`None`（Polars的空值）
+fn database_value_to_text(value: &DatabaseValue) -> Option<String> {
+    match value {
+        DatabaseValue::Null => None,
+        DatabaseValue::Bool(b) => Some(b.to_string()),
+        DatabaseValue::I32(i) => Some(i.to_string()),
+        DatabaseValue::I64(i) => Some(i.to_string()),
+        DatabaseValue::F32(f) => Some(f.to_string()),
+        DatabaseValue::F64(f) => Some(f.to_string()),
+        DatabaseValue::String(s) => Some(s.clone()),
+        DatabaseValue::Bytes(b) => Some(b.iter().map(|byte| format!("{:02x}", byte)).collect()),
+        DatabaseValue::DateTime(dt) => Some(dt.to_rfc3339()),
+        DatabaseValue::Uuid(u) => Some(u.to_string()),
+        DatabaseValue::Decimal(d) => Some(d.to_string()),
+        DatabaseValue::Date(d) => Some(d.to_string()),
+        DatabaseValue::Time(t) => Some(t.to_string()),
+        DatabaseValue::DateTimeTz(dt) => Some(dt.to_rfc3339()),
+    }
+}
+
 /// 连接池工厂特征
 #[async_trait]
 pub trait PoolFactory: Send + Sync {
@@ -96,4 +425,27 @@ pub trait PoolFactory: Send + Sync {
 
     /// 验证配置
     fn validate_config(config: &crate::core::types::DatabaseConfig) -> Result<()>;
+}
+
+#[cfg(test)]
+mod sample_table_tests {
+    use super::validate_sql_identifier;
+
+    #[test]
+    fn accepts_bare_and_schema_qualified_identifiers() {
+        assert!(validate_sql_identifier("users").is_ok());
+        assert!(validate_sql_identifier("_users").is_ok());
+        assert!(validate_sql_identifier("public.users").is_ok());
+        assert!(validate_sql_identifier("id").is_ok());
+    }
+
+    #[test]
+    fn rejects_injection_attempts() {
+        assert!(validate_sql_identifier("id; DROP TABLE users; --").is_err());
+        assert!(validate_sql_identifier("users; DROP TABLE users").is_err());
+        assert!(validate_sql_identifier("(SELECT 1)").is_err());
+        assert!(validate_sql_identifier("users --").is_err());
+        assert!(validate_sql_identifier("").is_err());
+        assert!(validate_sql_identifier("1users").is_err());
+    }
 }
\ No newline at end of file
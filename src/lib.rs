@@ -1,21 +1,38 @@
-use pyo3::prelude::*;
-
 pub mod core;
+
+/// `databases`/`utils`/`python`都经由各自的驱动（tiberius/tokio-postgres/rusqlite）
+/// 或pyo3传递性依赖了原生线程/网络/动态链接能力，`wasm32-unknown-unknown`下无法
+/// 编译。`core::types`/`core::error`/`core::config::ConfigManager`不依赖它们，
+/// 因此在`core`之外整体裁掉，而不是逐个驱动打补丁——效果上与quaint把native/wasm
+/// 拆成不同crate一致，只是这里用target cfg而非crate边界
+#[cfg(not(target_arch = "wasm32"))]
 pub mod databases;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod python;
+#[cfg(not(target_arch = "wasm32"))]
 pub mod utils;
 
-use python::pool::PyDatabasePool;
+#[cfg(not(target_arch = "wasm32"))]
+use pyo3::prelude::*;
+#[cfg(not(target_arch = "wasm32"))]
+use python::arrow::PyArrowStream;
+#[cfg(not(target_arch = "wasm32"))]
+use python::pool::{PyDatabasePool, PyPreparedStatement, PyResultStream, PyTransaction};
 
 /// db-pool-rs Python模块
+#[cfg(not(target_arch = "wasm32"))]
 #[pymodule]
 fn _db_pool_rs(_py: Python, m: &PyModule) -> PyResult<()> {
     // 添加版本信息
     m.add("__version__", env!("CARGO_PKG_VERSION"))?;
-    
+
     // 添加核心类 (导出为 DatabasePool)
     m.add_class::<PyDatabasePool>()?;
-    
+    m.add_class::<PyArrowStream>()?;
+    m.add_class::<PyResultStream>()?;
+    m.add_class::<PyPreparedStatement>()?;
+    m.add_class::<PyTransaction>()?;
+
     // 为Python兼容性添加别名
     m.add("DatabasePool", _py.get_type::<PyDatabasePool>())?;
     
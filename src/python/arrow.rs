@@ -0,0 +1,91 @@
+use crate::core::error::{ConversionError, Result};
+use polars::export::arrow;
+use polars::export::arrow::ffi;
+use polars::frame::DataFrame;
+use pyo3::ffi::Py_uintptr_t;
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+use std::ffi::CString;
+
+/// 将Polars DataFrame的Arrow分片打包为实现Arrow PyCapsule协议的对象
+///
+/// 消费方（pandas/polars/pyarrow）可以通过 `__arrow_c_stream__` 以零拷贝方式
+/// 读取底层的Arrow RecordBatch，而不必经过逐单元格的Python对象转换。
+#[pyclass(name = "ArrowStream")]
+pub struct PyArrowStream {
+    schema: arrow::datatypes::Schema,
+    chunks: Vec<arrow::chunk::Chunk<Box<dyn arrow::array::Array>>>,
+}
+
+impl PyArrowStream {
+    pub fn from_dataframe(df: DataFrame) -> Result<Self> {
+        let schema = df.schema().to_arrow();
+        let chunks = df
+            .iter_chunks()
+            .map(|chunk| chunk)
+            .collect::<Vec<_>>();
+
+        Ok(Self { schema, chunks })
+    }
+}
+
+#[pymethods]
+impl PyArrowStream {
+    /// Arrow PyCapsule协议入口：返回一个封装了 `ArrowArrayStream` 的capsule
+    #[pyo3(signature = (requested_schema = None))]
+    fn __arrow_c_stream__<'py>(
+        &self,
+        py: Python<'py>,
+        requested_schema: Option<&PyAny>,
+    ) -> PyResult<&'py PyCapsule> {
+        let _ = requested_schema; // 暂不支持schema投影请求
+
+        let field = arrow::datatypes::Field::new(
+            "",
+            arrow::datatypes::DataType::Struct(self.schema.fields.clone()),
+            false,
+        );
+
+        let iter = Box::new(self.chunks.clone().into_iter().map(|chunk| {
+            let arr: Box<dyn arrow::array::Array> =
+                Box::new(arrow::array::StructArray::new(
+                    field.data_type().clone(),
+                    chunk.into_arrays(),
+                    None,
+                ));
+            Ok(arr)
+        })) as Box<dyn Iterator<Item = std::result::Result<Box<dyn arrow::array::Array>, arrow::error::Error>>>;
+
+        let exported = ffi::export_iterator(iter, field);
+
+        let name = CString::new("arrow_array_stream").unwrap();
+        PyCapsule::new_with_destructor(
+            py,
+            exported,
+            Some(name.as_c_str()),
+            |stream, _ctx| unsafe {
+                let stream = Box::from_raw(stream);
+                if let Some(release) = stream.release {
+                    release(&mut *Box::into_raw(stream));
+                }
+            },
+        )
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "ArrowStream(chunks={}, columns={})",
+            self.chunks.len(),
+            self.schema.fields.len()
+        )
+    }
+}
+
+/// 获取capsule内部指针地址，便于诊断/测试
+pub fn capsule_address(capsule: &PyCapsule) -> Py_uintptr_t {
+    capsule.pointer() as Py_uintptr_t
+}
+
+pub fn dataframe_conversion_error(msg: impl Into<String>) -> crate::core::error::DbPoolError {
+    ConversionError::DataFrameConversion(msg.into()).into()
+}
@@ -1,6 +1,10 @@
+use crate::core::migrate::MigrationRunner;
 use crate::core::pool_manager::DistributedPoolManager;
+use crate::core::prepared::{ParamFormat, PreparedStatementHandle};
+use crate::databases::traits::TransactionSession;
 use crate::core::types::{DatabaseConfig, DatabaseType, DeploymentMode, PoolConfig, TimeoutConfig, QueryParams, DatabaseValue, BatchOperation};
 use crate::core::error::{DbPoolError, Result};
+use crate::python::arrow::PyArrowStream;
 use crate::utils::dataframe::DataFrameConverter;
 use pyo3::prelude::*;
 use pyo3::types::{PyDict, PyList, PyTuple};
@@ -8,6 +12,7 @@ use pyo3_asyncio::tokio::future_into_py;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use tokio::sync::Mutex;
 
 /// Python数据库连接池类
 #[pyclass(name = "DatabasePool")]
@@ -57,7 +62,9 @@ impl PyDatabasePool {
         max_lifetime = 3600,
         auto_scaling = true,
         health_check_interval = 60,
-        application_name = None
+        application_name = None,
+        allow_load_extension = false,
+        prepared_cache_size = 256
     ))]
     fn create_pool<'py>(
         &self,
@@ -77,6 +84,8 @@ impl PyDatabasePool {
         auto_scaling: bool,
         health_check_interval: u64,
         application_name: Option<String>,
+        allow_load_extension: bool,
+        prepared_cache_size: usize,
     ) -> PyResult<&'py PyAny> {
         let manager = Arc::clone(&self.manager);
         
@@ -113,6 +122,8 @@ impl PyDatabasePool {
             ssl_config: None,
             timeout_config: TimeoutConfig::default(),
             application_name,
+            allow_load_extension,
+            prepared_cache_size,
         };
 
         future_into_py::<_, PyObject>(py, async move {
@@ -152,6 +163,68 @@ impl PyDatabasePool {
         })
     }
 
+    /// 执行查询并以Arrow C Stream接口（PyCapsule）零拷贝返回结果
+    ///
+    /// 相比 `query`，这里跳过了逐单元格的Python对象构建，直接把内部DataFrame的
+    /// Arrow分片交给pandas/polars/pyarrow等消费方。
+    #[pyo3(signature = (pool_id, sql, params = None))]
+    fn query_arrow<'py>(
+        &self,
+        py: Python<'py>,
+        pool_id: String,
+        sql: String,
+        params: Option<&PyDict>,
+    ) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+        let query_params = if let Some(params_dict) = params {
+            Some(Self::py_dict_to_query_params(params_dict)?)
+        } else {
+            None
+        };
+
+        future_into_py::<_, PyObject>(py, async move {
+            let df = manager.execute_query(&pool_id, &sql, query_params).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            let stream = PyArrowStream::from_dataframe(df)
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(Py::new(py, stream)?.into_py(py)))
+        })
+    }
+
+    /// 以流式游标方式执行查询，返回一个Python异步迭代器
+    ///
+    /// 每次迭代只从连接池拉取 `batch_size` 行，避免一次性把整个结果集
+    /// 物化为DataFrame再物化为dict，从而限制大查询的内存占用。
+    #[pyo3(signature = (pool_id, sql, params = None, batch_size = 10000))]
+    fn query_stream<'py>(
+        &self,
+        py: Python<'py>,
+        pool_id: String,
+        sql: String,
+        params: Option<&PyDict>,
+        batch_size: usize,
+    ) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+        let query_params = if let Some(params_dict) = params {
+            Some(Self::py_dict_to_query_params(params_dict)?)
+        } else {
+            None
+        };
+
+        future_into_py::<_, PyObject>(py, async move {
+            let receiver = manager
+                .execute_query_stream(&pool_id, &sql, query_params, batch_size)
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| {
+                Ok(Py::new(py, PyResultStream::new(receiver))?.into_py(py))
+            })
+        })
+    }
+
     /// 执行非查询操作
     #[pyo3(signature = (pool_id, sql, params = None))]
     fn execute<'py>(
@@ -225,6 +298,8 @@ impl PyDatabasePool {
                 status_dict.set_item("is_healthy", status.is_healthy)?;
                 status_dict.set_item("last_error", status.last_error)?;
                 status_dict.set_item("uptime_seconds", status.uptime.as_secs())?;
+                status_dict.set_item("circuit_state", format!("{:?}", status.circuit_state))?;
+                status_dict.set_item("consecutive_failures", status.consecutive_failures)?;
                 Ok(status_dict.into())
             })
         })
@@ -244,11 +319,14 @@ impl PyDatabasePool {
                 metrics_dict.set_item("queries_per_second", metrics.queries_per_second)?;
                 metrics_dict.set_item("connection_utilization", metrics.connection_utilization)?;
                 metrics_dict.set_item("avg_query_time_ms", metrics.avg_query_time.as_millis())?;
+                metrics_dict.set_item("p50_query_time_ms", metrics.p50_query_time.as_millis())?;
                 metrics_dict.set_item("p99_query_time_ms", metrics.p99_query_time.as_millis())?;
+                metrics_dict.set_item("p999_query_time_ms", metrics.p999_query_time.as_millis())?;
                 metrics_dict.set_item("error_rate", metrics.error_rate)?;
                 metrics_dict.set_item("total_queries", metrics.total_queries)?;
                 metrics_dict.set_item("total_errors", metrics.total_errors)?;
                 metrics_dict.set_item("cache_hit_rate", metrics.cache_hit_rate)?;
+                metrics_dict.set_item("total_retries", metrics.total_retries)?;
                 Ok(metrics_dict.into())
             })
         })
@@ -259,6 +337,36 @@ impl PyDatabasePool {
         Ok(self.manager.list_pools())
     }
 
+    /// 将所有连接池的指标渲染为Prometheus文本暴露格式
+    fn render_prometheus<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let text = manager.render_prometheus().await;
+            Python::with_gil(|py| Ok(text.into_py(py)))
+        })
+    }
+
+    /// 在后台启动 `/metrics` HTTP端点（需启用 `metrics_http` feature），绑定到 `addr`
+    #[cfg(feature = "metrics_http")]
+    fn start_metrics_server<'py>(&self, py: Python<'py>, addr: String) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let server = crate::core::metrics_server::MetricsServer::bind(&addr, manager)
+                .await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            tokio::spawn(async move {
+                if let Err(e) = server.serve().await {
+                    tracing::error!("metrics端点退出: {}", e);
+                }
+            });
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
     /// 移除连接池
     fn remove_pool<'py>(&self, py: Python<'py>, pool_id: String) -> PyResult<&'py PyAny> {
         let manager = Arc::clone(&self.manager);
@@ -287,6 +395,134 @@ impl PyDatabasePool {
         })
     }
 
+    /// 准备一条语句，返回可重复执行并能固定参数类型/格式的 `PreparedStatement`
+    fn prepare<'py>(&self, py: Python<'py>, pool_id: String, sql: String) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let handle = manager.prepare(&pool_id, &sql).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| {
+                Ok(Py::new(py, PyPreparedStatement::new(manager, handle))?.into_py(py))
+            })
+        })
+    }
+
+    /// 从连接池租用一个连接并开启事务，返回支持 `async with` 的 `Transaction`
+    fn begin<'py>(&self, py: Python<'py>, pool_id: String) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let session = manager.begin(&pool_id).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(Py::new(py, PyTransaction::new(session))?.into_py(py)))
+        })
+    }
+
+    /// 应用 `migrations_dir` 中所有尚未执行的迁移，返回新应用的版本号列表
+    fn migrate<'py>(&self, py: Python<'py>, pool_id: String, migrations_dir: String) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let dir = std::path::PathBuf::from(migrations_dir);
+            let applied = MigrationRunner::migrate(&manager, &pool_id, &dir).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(applied.into_py(py)))
+        })
+    }
+
+    /// 回滚/前进到指定版本（使用down脚本），返回被回滚的版本号列表
+    fn migrate_to<'py>(
+        &self,
+        py: Python<'py>,
+        pool_id: String,
+        migrations_dir: String,
+        version: u64,
+    ) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let dir = std::path::PathBuf::from(migrations_dir);
+            let reverted = MigrationRunner::migrate_to(&manager, &pool_id, &dir, version).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(reverted.into_py(py)))
+        })
+    }
+
+    /// 在线增量备份SQLite数据库到目标路径（仅SQLite后端支持）
+    #[pyo3(signature = (pool_id, dest_path, pages_per_step = 100))]
+    fn sqlite_backup<'py>(
+        &self,
+        py: Python<'py>,
+        pool_id: String,
+        dest_path: String,
+        pages_per_step: i32,
+    ) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            manager.backup_to(&pool_id, &dest_path, pages_per_step).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 注册一个Python标量函数，之后在该连接池上执行的SQL可直接按名称调用（仅SQLite后端支持）
+    #[pyo3(signature = (pool_id, name, arity, callable))]
+    fn sqlite_register_function<'py>(
+        &self,
+        py: Python<'py>,
+        pool_id: String,
+        name: String,
+        arity: i32,
+        callable: PyObject,
+    ) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+        let callable = Arc::new(callable);
+
+        let func: Arc<dyn Fn(Vec<DatabaseValue>) -> Result<DatabaseValue> + Send + Sync> =
+            Arc::new(move |args: Vec<DatabaseValue>| {
+                Python::with_gil(|py| {
+                    let py_args: PyResult<Vec<PyObject>> = args
+                        .into_iter()
+                        .map(|v| Self::database_value_to_python_owned(py, v))
+                        .collect();
+                    let py_args = py_args.map_err(|e| DbPoolError::Runtime(e.to_string()))?;
+
+                    let result = callable
+                        .call1(py, PyTuple::new(py, py_args))
+                        .map_err(|e| DbPoolError::Runtime(e.to_string()))?;
+
+                    Self::py_any_to_database_value(result.as_ref(py))
+                        .map_err(|e| DbPoolError::Runtime(e.to_string()))
+                })
+            });
+
+        future_into_py::<_, PyObject>(py, async move {
+            manager.register_scalar_function(&pool_id, &name, arity, func).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 加载一个SQLite扩展（需要在创建连接池时设置`allow_load_extension=True`）
+    fn sqlite_load_extension<'py>(&self, py: Python<'py>, pool_id: String, path: String) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+
+        future_into_py::<_, PyObject>(py, async move {
+            manager.load_extension(&pool_id, &path).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
     /// 获取版本信息
     #[staticmethod]
     fn version() -> String {
@@ -320,6 +556,26 @@ impl PyDatabasePool {
         Ok(params)
     }
 
+    /// 将数据库值转换为Python对象，供标量函数的参数传递给Python回调使用
+    fn database_value_to_python_owned(py: Python, value: DatabaseValue) -> PyResult<PyObject> {
+        Ok(match value {
+            DatabaseValue::Null => py.None(),
+            DatabaseValue::Bool(b) => b.into_py(py),
+            DatabaseValue::I32(i) => i.into_py(py),
+            DatabaseValue::I64(i) => i.into_py(py),
+            DatabaseValue::F32(f) => f.into_py(py),
+            DatabaseValue::F64(f) => f.into_py(py),
+            DatabaseValue::String(s) => s.into_py(py),
+            DatabaseValue::Bytes(b) => b.into_py(py),
+            DatabaseValue::DateTime(dt) => dt.timestamp_millis().into_py(py),
+            DatabaseValue::Uuid(u) => u.to_string().into_py(py),
+            DatabaseValue::Decimal(d) => d.to_string().into_py(py),
+            DatabaseValue::Date(d) => d.to_string().into_py(py),
+            DatabaseValue::Time(t) => t.to_string().into_py(py),
+            DatabaseValue::DateTimeTz(dt) => dt.timestamp_millis().into_py(py),
+        })
+    }
+
     /// 将Python值转换为数据库值
     fn py_any_to_database_value(value: &PyAny) -> PyResult<DatabaseValue> {
         if value.is_none() {
@@ -443,4 +699,268 @@ impl PyDatabasePool {
             }
         }
     }
+}
+
+/// `query_stream` 返回的Python异步迭代器
+///
+/// 包装一个接收小批量DataFrame的channel；`__anext__` 每次只等待下一批，
+/// 由 `DistributedPoolManager::execute_query_stream` 的生产者任务提供背压。
+#[pyclass(name = "ResultStream")]
+pub struct PyResultStream {
+    receiver: Arc<Mutex<tokio::sync::mpsc::Receiver<Result<polars::frame::DataFrame>>>>,
+}
+
+impl PyResultStream {
+    pub fn new(receiver: tokio::sync::mpsc::Receiver<Result<polars::frame::DataFrame>>) -> Self {
+        Self {
+            receiver: Arc::new(Mutex::new(receiver)),
+        }
+    }
+}
+
+#[pymethods]
+impl PyResultStream {
+    fn __aiter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    fn __anext__<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let receiver = Arc::clone(&self.receiver);
+
+        future_into_py::<_, PyObject>(py, async move {
+            let mut receiver = receiver.lock().await;
+            match receiver.recv().await {
+                Some(Ok(df)) => Python::with_gil(|py| PyDatabasePool::polars_df_to_python(py, df)),
+                Some(Err(e)) => Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string())),
+                None => Err(PyErr::new::<pyo3::exceptions::PyStopAsyncIteration, _>(())),
+            }
+        })
+    }
+}
+
+/// `pool.prepare()` 返回的预编译语句句柄
+///
+/// 内部按SQL文本在 `DistributedPoolManager` 的缓存中登记一次，之后每次
+/// `execute` 都复用同一缓存项的类型元数据，跳过重新解析。
+#[pyclass(name = "PreparedStatement")]
+pub struct PyPreparedStatement {
+    manager: Arc<DistributedPoolManager>,
+    handle: PreparedStatementHandle,
+}
+
+impl PyPreparedStatement {
+    fn new(manager: Arc<DistributedPoolManager>, handle: PreparedStatementHandle) -> Self {
+        Self { manager, handle }
+    }
+}
+
+#[pymethods]
+impl PyPreparedStatement {
+    /// 为某个位置的参数固定声明类型OID（覆盖自动推断）
+    fn pin_param_type<'py>(&self, py: Python<'py>, index: usize, type_oid: i32) -> PyResult<&'py PyAny> {
+        let handle = self.handle.clone();
+        future_into_py::<_, PyObject>(py, async move {
+            handle.pin_param_type(index, type_oid).await;
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 为某个位置的参数固定文本/二进制编码格式
+    fn set_param_format<'py>(&self, py: Python<'py>, index: usize, binary: bool) -> PyResult<&'py PyAny> {
+        let handle = self.handle.clone();
+        let format = if binary { ParamFormat::Binary } else { ParamFormat::Text };
+        future_into_py::<_, PyObject>(py, async move {
+            handle.set_param_format(index, format).await;
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 以位置或命名参数重复执行该语句
+    #[pyo3(signature = (params = None))]
+    fn execute<'py>(&self, py: Python<'py>, params: Option<&PyDict>) -> PyResult<&'py PyAny> {
+        let manager = Arc::clone(&self.manager);
+        let handle = self.handle.clone();
+        let query_params = if let Some(params_dict) = params {
+            Some(PyDatabasePool::py_dict_to_query_params(params_dict)?)
+        } else {
+            None
+        };
+
+        future_into_py::<_, PyObject>(py, async move {
+            let df = manager.execute_prepared(&handle, query_params).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| PyDatabasePool::polars_df_to_python(py, df))
+        })
+    }
+
+    fn sql(&self) -> String {
+        self.handle.sql.clone()
+    }
+}
+
+/// `pool.begin()` 返回的事务句柄，支持 `async with` 语义
+///
+/// 持有从池中租用的 `TransactionSession`；`__aexit__` 在正常退出时提交，
+/// 在异常退出或显式调用 `rollback` 后回滚，确保租用的连接总能被归还/关闭。
+#[pyclass(name = "Transaction")]
+pub struct PyTransaction {
+    session: Arc<Mutex<Option<Box<dyn TransactionSession>>>>,
+}
+
+impl PyTransaction {
+    fn new(session: Box<dyn TransactionSession>) -> Self {
+        Self {
+            session: Arc::new(Mutex::new(Some(session))),
+        }
+    }
+}
+
+#[pymethods]
+impl PyTransaction {
+    /// 在该事务内执行查询
+    #[pyo3(signature = (sql, params = None))]
+    fn query<'py>(&self, py: Python<'py>, sql: String, params: Option<&PyDict>) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        let query_params = if let Some(params_dict) = params {
+            Some(PyDatabasePool::py_dict_to_query_params(params_dict)?)
+        } else {
+            None
+        };
+
+        future_into_py::<_, PyObject>(py, async move {
+            let mut guard = session.lock().await;
+            let session = guard.as_mut()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("事务已结束"))?;
+            let df = session.query(&sql, query_params).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| PyDatabasePool::polars_df_to_python(py, df))
+        })
+    }
+
+    /// 在该事务内执行非查询操作
+    #[pyo3(signature = (sql, params = None))]
+    fn execute<'py>(&self, py: Python<'py>, sql: String, params: Option<&PyDict>) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        let query_params = if let Some(params_dict) = params {
+            Some(PyDatabasePool::py_dict_to_query_params(params_dict)?)
+        } else {
+            None
+        };
+
+        future_into_py::<_, PyObject>(py, async move {
+            let mut guard = session.lock().await;
+            let session = guard.as_mut()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("事务已结束"))?;
+            let affected = session.execute(&sql, query_params).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(affected.into_py(py)))
+        })
+    }
+
+    /// 创建保存点
+    fn savepoint<'py>(&self, py: Python<'py>, name: String) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        future_into_py::<_, PyObject>(py, async move {
+            let mut guard = session.lock().await;
+            let session = guard.as_mut()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("事务已结束"))?;
+            session.savepoint(&name).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 释放保存点
+    fn release_savepoint<'py>(&self, py: Python<'py>, name: String) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        future_into_py::<_, PyObject>(py, async move {
+            let mut guard = session.lock().await;
+            let session = guard.as_mut()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("事务已结束"))?;
+            session.release_savepoint(&name).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 回滚到指定保存点
+    fn rollback_to<'py>(&self, py: Python<'py>, name: String) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        future_into_py::<_, PyObject>(py, async move {
+            let mut guard = session.lock().await;
+            let session = guard.as_mut()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("事务已结束"))?;
+            session.rollback_to(&name).await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 提交事务
+    fn commit<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        future_into_py::<_, PyObject>(py, async move {
+            let mut taken = session.lock().await.take()
+                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>("事务已结束"))?;
+            taken.commit().await
+                .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// 回滚事务
+    fn rollback<'py>(&self, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        future_into_py::<_, PyObject>(py, async move {
+            if let Some(mut taken) = session.lock().await.take() {
+                taken.rollback().await
+                    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            }
+
+            Python::with_gil(|py| Ok(py.None()))
+        })
+    }
+
+    /// `async with` 入口，返回自身
+    fn __aenter__<'py>(slf: PyRef<'py, Self>, py: Python<'py>) -> PyResult<&'py PyAny> {
+        let slf: Py<Self> = slf.into();
+        future_into_py::<_, PyObject>(py, async move {
+            Python::with_gil(|py| Ok(slf.into_py(py)))
+        })
+    }
+
+    /// `async with` 退出：无异常则提交，有异常或已手动结束则回滚
+    #[pyo3(signature = (exc_type, exc_value, traceback))]
+    fn __aexit__<'py>(
+        &self,
+        py: Python<'py>,
+        exc_type: &PyAny,
+        exc_value: &PyAny,
+        traceback: &PyAny,
+    ) -> PyResult<&'py PyAny> {
+        let session = Arc::clone(&self.session);
+        let has_exception = !exc_type.is_none();
+        let _ = exc_value;
+        let _ = traceback;
+
+        future_into_py::<_, PyObject>(py, async move {
+            if let Some(mut taken) = session.lock().await.take() {
+                let result = if has_exception {
+                    taken.rollback().await
+                } else {
+                    taken.commit().await
+                };
+                result.map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(e.to_string()))?;
+            }
+
+            Python::with_gil(|py| Ok(false.into_py(py)))
+        })
+    }
 }
\ No newline at end of file
@@ -0,0 +1,203 @@
+use crate::utils::monitoring::AlertView;
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+use tokio_rustls::TlsConnector;
+
+/// 告警通知出口的扩展点：`AlertManager::register_sink`接受任意实现，触发/解决
+/// 事件会在原本广播给 `/alerts` SSE订阅者的同时逐个喂给已注册的sink
+///
+/// `notify`失败只应记录日志，不向调用方传播——一个sink的故障不应影响告警
+/// 本身的触发/解决判定，也不应拖慢 `evaluate_alerts`
+#[async_trait]
+pub trait AlertSink: Send + Sync {
+    /// sink名称，仅用于失败日志中标识是哪个sink
+    fn name(&self) -> &str;
+
+    /// 推送一次告警触发/解决事件
+    async fn notify(&self, event: &AlertView);
+}
+
+/// Webhook负载格式：不同下游服务期望的JSON结构不同，但都是同一条HTTP POST
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WebhookFormat {
+    /// 直接POST `AlertView`本身的JSON序列化结果
+    Generic,
+    /// Slack incoming webhook所需的 `{"text": "..."}` 结构
+    Slack,
+    /// PagerDuty Events API v2所需的 `{"routing_key", "event_action", "payload"}` 结构；
+    /// `routing_key`由调用方在构造时提供（即PagerDuty的集成密钥）
+    PagerDuty { routing_key: String },
+}
+
+/// 经原始TCP（或TLS）连接向一个HTTP(S) endpoint POST告警事件的sink；与
+/// `monitoring_server::MonitoringServer`对称——那里是手写最小HTTP服务端，
+/// 这里是手写最小HTTP客户端，都不引入完整的HTTP框架
+///
+/// `WebhookFormat::Slack`/`PagerDuty`对应的incoming webhook与Events API v2都是
+/// HTTPS-only端点，因此`use_tls=true`时用`core::tls::default_roots_client_config`
+/// 的标准公共CA校验建立连接；`use_tls=false`仅用于内网/自建的明文webhook目标。
+pub struct WebhookSink {
+    name: String,
+    host: String,
+    port: u16,
+    path: String,
+    format: WebhookFormat,
+    use_tls: bool,
+}
+
+impl WebhookSink {
+    /// `host`/`port`/`path`需调用方拆分好（例如从 `http://host:port/path` 手动解析），
+    /// 本类型不做URL解析，只负责按 `format` 编码请求体并发出一次HTTP/1.1 POST；
+    /// `use_tls`决定是走TLS还是明文TCP——对接Slack/PagerDuty等公网服务必须为`true`
+    pub fn new(
+        name: impl Into<String>,
+        host: impl Into<String>,
+        port: u16,
+        path: impl Into<String>,
+        format: WebhookFormat,
+        use_tls: bool,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            host: host.into(),
+            port,
+            path: path.into(),
+            format,
+            use_tls,
+        }
+    }
+
+    fn render_body(&self, event: &AlertView) -> String {
+        match &self.format {
+            WebhookFormat::Generic => serde_json::to_string(event).unwrap_or_default(),
+            WebhookFormat::Slack => {
+                let text = format!(
+                    "[{:?}] {} ({:?})",
+                    event.severity, event.message, event.kind
+                );
+                serde_json::json!({ "text": text }).to_string()
+            }
+            WebhookFormat::PagerDuty { routing_key } => {
+                let event_action = match event.kind {
+                    crate::utils::monitoring::AlertEventKind::Triggered => "trigger",
+                    crate::utils::monitoring::AlertEventKind::Resolved => "resolve",
+                };
+                serde_json::json!({
+                    "routing_key": routing_key,
+                    "event_action": event_action,
+                    "dedup_key": event.id,
+                    "payload": {
+                        "summary": event.message,
+                        "severity": format!("{:?}", event.severity).to_lowercase(),
+                        "source": event.pool_id.clone().unwrap_or_else(|| "system".to_string()),
+                    }
+                })
+                .to_string()
+            }
+        }
+    }
+
+    async fn post(&self, body: &str) -> std::io::Result<()> {
+        let tcp_stream = TcpStream::connect((self.host.as_str(), self.port)).await?;
+
+        let request = format!(
+            "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.path,
+            self.host,
+            body.len(),
+            body
+        );
+
+        if self.use_tls {
+            let connector = TlsConnector::from(crate::core::tls::default_roots_client_config());
+            let server_name = rustls::ServerName::try_from(self.host.as_str())
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+            let mut stream = connector.connect(server_name, tcp_stream).await?;
+            stream.write_all(request.as_bytes()).await?;
+
+            // 丢弃响应体，只关心连接层面的错误；下游返回的4xx/5xx不会让notify失败
+            let mut discard = [0u8; 256];
+            while stream.read(&mut discard).await? > 0 {}
+        } else {
+            let mut stream = tcp_stream;
+            stream.write_all(request.as_bytes()).await?;
+
+            let mut discard = [0u8; 256];
+            while stream.read(&mut discard).await? > 0 {}
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl AlertSink for WebhookSink {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    async fn notify(&self, event: &AlertView) {
+        let body = self.render_body(event);
+        if let Err(e) = self.post(&body).await {
+            tracing::warn!("告警sink {} 推送失败: {}", self.name, e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::monitoring::{AlertEventKind, AlertSeverity, AlertView};
+    use tokio::io::AsyncReadExt as _;
+    use tokio::net::TcpListener;
+
+    fn sample_event() -> AlertView {
+        AlertView {
+            id: "alert-1".to_string(),
+            rule_id: "rule-1".to_string(),
+            pool_id: Some("pool-1".to_string()),
+            message: "pool exhausted".to_string(),
+            severity: AlertSeverity::Critical,
+            kind: AlertEventKind::Triggered,
+        }
+    }
+
+    /// `use_tls=false`时应原样走明文TCP，`post`不应尝试TLS握手
+    #[tokio::test]
+    async fn use_tls_false_sends_plaintext_http_request() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let accept = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut buf = Vec::new();
+            let mut chunk = [0u8; 1024];
+            loop {
+                let n = stream.read(&mut chunk).await.unwrap();
+                if n == 0 {
+                    break;
+                }
+                buf.extend_from_slice(&chunk[..n]);
+                if buf.windows(4).any(|w| w == b"\r\n\r\n") {
+                    break;
+                }
+            }
+            String::from_utf8(buf).unwrap()
+        });
+
+        let sink = WebhookSink::new(
+            "test-sink",
+            addr.ip().to_string(),
+            addr.port(),
+            "/hooks/alerts",
+            WebhookFormat::Generic,
+            false,
+        );
+        sink.notify(&sample_event()).await;
+
+        let received = accept.await.unwrap();
+        assert!(received.starts_with("POST /hooks/alerts HTTP/1.1"));
+        assert!(received.contains("alert-1"));
+    }
+}
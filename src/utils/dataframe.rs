@@ -5,6 +5,15 @@ use std::collections::HashMap;
 /// DataFrame转换工具
 pub struct DataFrameConverter;
 
+/// 整列扫描后解析出的schema：类型提升到的最窄公共类型、是否存在空值、列的位置
+struct SchemaInfo {
+    dtype: DataType,
+    #[allow(dead_code)]
+    nullable: bool,
+    #[allow(dead_code)]
+    index: usize,
+}
+
 impl DataFrameConverter {
     /// 将HashMap转换为DataFrame
     pub fn from_hashmap(data: HashMap<String, Vec<AnyValue>>) -> Result<DataFrame> {
@@ -123,19 +132,51 @@ impl DataFrameConverter {
         let memory_usage = df.estimated_size();
         stats.insert("memory_usage_bytes".to_string(), serde_json::json!(memory_usage));
 
+        // 逐列观测到的最小/最大值
+        let mut min_max = HashMap::new();
+        for col_name in df.get_column_names() {
+            if let Ok(series) = df.column(col_name) {
+                let (min_value, max_value) = Self::series_min_max_json(series);
+                min_max.insert(col_name.to_string(), serde_json::json!({
+                    "min": min_value,
+                    "max": max_value
+                }));
+            }
+        }
+        stats.insert("min_max".to_string(), serde_json::json!(min_max));
+
         Ok(stats)
     }
 
     // 私有辅助方法
 
+    /// 一列观测到的最小/最大值，转换为JSON；取不到（如全空列）时落为`null`
+    fn series_min_max_json(series: &Series) -> (serde_json::Value, serde_json::Value) {
+        let min_value = series
+            .min_as_series()
+            .get(0)
+            .ok()
+            .and_then(|v| Self::any_value_to_json_value(v).ok())
+            .unwrap_or(serde_json::Value::Null);
+        let max_value = series
+            .max_as_series()
+            .get(0)
+            .ok()
+            .and_then(|v| Self::any_value_to_json_value(v).ok())
+            .unwrap_or(serde_json::Value::Null);
+
+        (min_value, max_value)
+    }
+
     /// 从AnyValue向量创建Series
     fn create_series_from_any_values(name: &str, values: Vec<AnyValue>) -> Result<Series> {
         if values.is_empty() {
             return Ok(Series::new_empty(name, &DataType::Null));
         }
 
-        // 推断数据类型
-        let data_type = Self::infer_data_type(&values);
+        // 对整列做两遍扫描，解析出能容纳所有值的最窄类型
+        let schema = Self::resolve_column_schema(&values, 0);
+        let data_type = schema.dtype;
 
         match data_type {
             DataType::Boolean => {
@@ -198,6 +239,31 @@ impl DataFrameConverter {
                     .collect();
                 Ok(Series::new(name, string_values))
             }
+            DataType::Binary => {
+                let binary_values: Vec<Option<Vec<u8>>> = values
+                    .into_iter()
+                    .map(|v| match v {
+                        AnyValue::Binary(b) => Some(b.to_vec()),
+                        AnyValue::BinaryOwned(b) => Some(b),
+                        AnyValue::Null => None,
+                        _ => None,
+                    })
+                    .collect();
+                Ok(Series::new(name, binary_values))
+            }
+            DataType::Datetime(time_unit, tz) => {
+                let datetime_values: Vec<Option<i64>> = values
+                    .into_iter()
+                    .map(|v| match v {
+                        AnyValue::Datetime(dt, _, _) => Some(dt),
+                        AnyValue::Null => None,
+                        _ => None,
+                    })
+                    .collect();
+                Series::new(name, datetime_values)
+                    .cast(&DataType::Datetime(time_unit, tz))
+                    .map_err(|e| ConversionError::TypeConversion(e.to_string()).into())
+            }
             _ => {
                 // 默认转换为字符串
                 let string_values: Vec<Option<String>> = values
@@ -212,24 +278,67 @@ impl DataFrameConverter {
         }
     }
 
-    /// 推断数据类型
-    fn infer_data_type(values: &[AnyValue]) -> DataType {
+    /// 整列的schema解析结果：解析出的类型、是否存在空值、列的位置
+    ///
+    /// 由`resolve_column_schema`产出，`from_json_string`经由`from_hashmap`复用同一套
+    /// 宽化逻辑，不需要单独再扫一遍。
+    fn resolve_column_schema(values: &[AnyValue], index: usize) -> SchemaInfo {
+        let mut dtype = DataType::Null;
+        let mut nullable = false;
+
         for value in values {
-            match value {
-                AnyValue::Boolean(_) => return DataType::Boolean,
-                AnyValue::Int32(_) => return DataType::Int32,
-                AnyValue::Int64(_) => return DataType::Int64,
-                AnyValue::Float32(_) | AnyValue::Float64(_) => return DataType::Float64,
-                AnyValue::String(_) | AnyValue::StringOwned(_) => return DataType::String,
-                AnyValue::Datetime(_, time_unit, _) => {
-                    return DataType::Datetime(*time_unit, None);
-                }
-                AnyValue::Binary(_) | AnyValue::BinaryOwned(_) => return DataType::Binary,
-                AnyValue::Null => continue,
-                _ => return DataType::String,
+            if matches!(value, AnyValue::Null) {
+                nullable = true;
+                continue;
             }
+            dtype = Self::widen_dtype(dtype, Self::any_value_dtype(value));
+        }
+
+        SchemaInfo {
+            dtype,
+            nullable,
+            index,
+        }
+    }
+
+    /// 单个AnyValue自身的“基础”类型，不考虑和其它值的宽化
+    fn any_value_dtype(value: &AnyValue) -> DataType {
+        match value {
+            AnyValue::Boolean(_) => DataType::Boolean,
+            AnyValue::Int32(_) => DataType::Int32,
+            AnyValue::Int64(_) => DataType::Int64,
+            AnyValue::Float32(_) | AnyValue::Float64(_) => DataType::Float64,
+            AnyValue::String(_) | AnyValue::StringOwned(_) => DataType::String,
+            AnyValue::Datetime(_, time_unit, tz) => DataType::Datetime(*time_unit, tz.clone()),
+            AnyValue::Binary(_) | AnyValue::BinaryOwned(_) => DataType::Binary,
+            // 其余类型（Decimal/Date/Time等）走字符串兜底，和旧行为保持一致
+            _ => DataType::String,
+        }
+    }
+
+    /// 类型格的最小上界：按 Null → Boolean → Int32 → Int64 → Float64 → String 的格提升，
+    /// Binary/Datetime各自成一支，一旦和格内其它分支冲突就退回String
+    fn widen_dtype(current: DataType, incoming: DataType) -> DataType {
+        use DataType::*;
+
+        match (current, incoming) {
+            (Null, t) | (t, Null) => t,
+            (Boolean, Boolean) => Boolean,
+            (Int32, Int32) => Int32,
+            (Int64, Int64) => Int64,
+            (Int32, Int64) | (Int64, Int32) => Int64,
+            (Float64, Float64) => Float64,
+            (Int32, Float64) | (Float64, Int32) => Float64,
+            (Int64, Float64) | (Float64, Int64) => Float64,
+            (Binary, Binary) => Binary,
+            (Datetime(tu_a, tz_a), Datetime(tu_b, tz_b)) if tu_a == tu_b && tz_a == tz_b => {
+                Datetime(tu_a, tz_a)
+            }
+            (String, String) => String,
+            // 任何其它组合（含Boolean+数值、Int/Float+String、Binary/Datetime与非自身类型冲突）
+            // 都没有公共上界，统一提升为String
+            _ => String,
         }
-        DataType::Null
     }
 
     /// 将AnyValue转换为JSON值
@@ -334,7 +443,64 @@ mod base64 {
             
             i += 3;
         }
-        
+
         result
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn widens_mixed_integer_and_float_column_instead_of_dropping_values() {
+        let values = vec![AnyValue::Int64(1), AnyValue::Float64(2.5), AnyValue::Int64(3)];
+        let series = DataFrameConverter::create_series_from_any_values("col", values).unwrap();
+        assert_eq!(series.dtype(), &DataType::Float64);
+        let floats: Vec<Option<f64>> = series.f64().unwrap().into_iter().collect();
+        assert_eq!(floats, vec![Some(1.0), Some(2.5), Some(3.0)]);
+    }
+
+    #[test]
+    fn widens_mixed_numeric_and_text_column_to_string() {
+        let values = vec![AnyValue::Int64(1), AnyValue::StringOwned("two".into())];
+        let series = DataFrameConverter::create_series_from_any_values("col", values).unwrap();
+        assert_eq!(series.dtype(), &DataType::String);
+        assert_eq!(series.len(), 2);
+    }
+
+    #[test]
+    fn widens_int32_and_int64_column_to_int64() {
+        let values = vec![AnyValue::Int32(1), AnyValue::Int64(2), AnyValue::Int32(3)];
+        let series = DataFrameConverter::create_series_from_any_values("col", values).unwrap();
+        assert_eq!(series.dtype(), &DataType::Int64);
+        let ints: Vec<Option<i64>> = series.i64().unwrap().into_iter().collect();
+        assert_eq!(ints, vec![Some(1), Some(2), Some(3)]);
+    }
+
+    #[test]
+    fn null_values_do_not_force_widening_to_string() {
+        let values = vec![AnyValue::Int64(1), AnyValue::Null, AnyValue::Int64(3)];
+        let series = DataFrameConverter::create_series_from_any_values("col", values).unwrap();
+        assert_eq!(series.dtype(), &DataType::Int64);
+        let ints: Vec<Option<i64>> = series.i64().unwrap().into_iter().collect();
+        assert_eq!(ints, vec![Some(1), None, Some(3)]);
+    }
+
+    #[test]
+    fn from_hashmap_widens_each_column_independently() {
+        let mut data = HashMap::new();
+        data.insert(
+            "mixed_numeric".to_string(),
+            vec![AnyValue::Int64(1), AnyValue::Float64(2.5)],
+        );
+        data.insert(
+            "mixed_text".to_string(),
+            vec![AnyValue::Int64(1), AnyValue::StringOwned("two".into())],
+        );
+
+        let df = DataFrameConverter::from_hashmap(data).unwrap();
+        assert_eq!(df.column("mixed_numeric").unwrap().dtype(), &DataType::Float64);
+        assert_eq!(df.column("mixed_text").unwrap().dtype(), &DataType::String);
+    }
 }
\ No newline at end of file
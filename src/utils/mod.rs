@@ -0,0 +1,7 @@
+pub mod alert_sinks;
+pub mod dataframe;
+pub mod monitoring;
+pub mod quantile;
+
+#[cfg(feature = "monitoring_server")]
+pub mod monitoring_server;
@@ -1,8 +1,15 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
-use tokio::sync::RwLock;
+use tokio::sync::{broadcast, oneshot, RwLock};
 use serde::{Serialize, Deserialize};
+use crate::core::types::CircuitState;
+use crate::databases::traits::DatabasePool;
+use crate::utils::quantile::CkmsQuantile;
+
+/// 分位数草图的相对误差：查询`phi`分位数时，返回值的真实排名与`phi*n`的偏差
+/// 不超过`QUANTILE_EPSILON*n`
+const QUANTILE_EPSILON: f64 = 0.01;
 
 /// 监控工具集合
 pub struct MonitoringTools {
@@ -31,6 +38,242 @@ impl MonitoringTools {
     pub fn alerts(&self) -> Arc<AlertManager> {
         Arc::clone(&self.alert_manager)
     }
+
+    /// 启动一个后台监控worker：按`config.interval`周期性刷新系统指标、对每个
+    /// 已在`MetricsCollector`留有记录的连接池跑一次健康检查，再把所有连接池
+    /// 摘要逐个喂给`AlertManager::evaluate_alerts`
+    ///
+    /// 单个任务持有三套`Arc`克隆，用`tokio::select!`在定时器与关闭信号之间
+    /// 选择；返回的`WorkerHandle`支持`stop().await`优雅关闭，drop时也会发出
+    /// 关闭信号（但不等待任务退出）。
+    pub fn spawn_worker(&self, config: MonitorConfig) -> WorkerHandle {
+        let metrics_collector = Arc::clone(&self.metrics_collector);
+        let health_checker = Arc::clone(&self.health_checker);
+        let alert_manager = Arc::clone(&self.alert_manager);
+        let last_tick = Arc::new(RwLock::new(None));
+        let last_tick_writer = Arc::clone(&last_tick);
+        let (shutdown_tx, mut shutdown_rx) = oneshot::channel();
+
+        let join_handle = tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(config.interval);
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        metrics_collector.update_system_metrics().await;
+
+                        for pool_id in metrics_collector.known_pool_ids().await {
+                            let _ = health_checker.check_pool_health(&pool_id).await;
+                        }
+
+                        for summary in metrics_collector.get_all_pool_metrics().await.into_values() {
+                            alert_manager.evaluate_alerts(&summary).await;
+                        }
+
+                        *last_tick_writer.write().await = Some(Instant::now());
+                    }
+                    _ = &mut shutdown_rx => {
+                        break;
+                    }
+                }
+            }
+        });
+
+        WorkerHandle {
+            shutdown_tx: Some(shutdown_tx),
+            join_handle: Some(join_handle),
+            last_tick,
+        }
+    }
+
+    /// 以Prometheus文本暴露格式渲染所有连接池的吞吐/错误率/连接数/延迟分位数，
+    /// `check_system_health`给出的系统健康状态，以及按严重级别分组的活跃告警
+    /// 数量，供 `/metrics` 端点使用
+    pub async fn render_prometheus(&self) -> String {
+        let mut out = String::new();
+        let pool_metrics = self.metrics_collector.get_all_pool_metrics().await;
+
+        out.push_str("# HELP db_pool_queries_per_second 连接池每秒查询数\n");
+        out.push_str("# TYPE db_pool_queries_per_second gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_queries_per_second{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.queries_per_second
+            ));
+        }
+
+        out.push_str("# HELP db_pool_error_rate 连接池查询错误率\n");
+        out.push_str("# TYPE db_pool_error_rate gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_error_rate{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.error_rate
+            ));
+        }
+
+        out.push_str("# HELP db_pool_connection_utilization 连接池连接使用率\n");
+        out.push_str("# TYPE db_pool_connection_utilization gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_connection_utilization{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.connection_utilization
+            ));
+        }
+
+        out.push_str("# HELP db_pool_active_connections 连接池当前活跃连接数\n");
+        out.push_str("# TYPE db_pool_active_connections gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_active_connections{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.active_connections
+            ));
+        }
+
+        out.push_str("# HELP db_pool_total_connections 连接池总连接数\n");
+        out.push_str("# TYPE db_pool_total_connections gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_total_connections{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.total_connections
+            ));
+        }
+
+        out.push_str("# HELP db_pool_p50_latency_ms 连接池P50查询延迟（毫秒）\n");
+        out.push_str("# TYPE db_pool_p50_latency_ms gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_p50_latency_ms{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.p50_latency_ms
+            ));
+        }
+
+        out.push_str("# HELP db_pool_p95_latency_ms 连接池P95查询延迟（毫秒）\n");
+        out.push_str("# TYPE db_pool_p95_latency_ms gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_p95_latency_ms{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.p95_latency_ms
+            ));
+        }
+
+        out.push_str("# HELP db_pool_p99_latency_ms 连接池P99查询延迟（毫秒）\n");
+        out.push_str("# TYPE db_pool_p99_latency_ms gauge\n");
+        for summary in pool_metrics.values() {
+            out.push_str(&format!(
+                "db_pool_p99_latency_ms{{pool_id=\"{}\"}} {}\n",
+                escape_label(&summary.pool_id),
+                summary.p99_latency_ms
+            ));
+        }
+
+        let health = self.health_checker.check_system_health().await;
+        out.push_str("# HELP db_pool_system_healthy 系统整体健康状态（1=健康，0=不健康）\n");
+        out.push_str("# TYPE db_pool_system_healthy gauge\n");
+        out.push_str(&format!("db_pool_system_healthy {}\n", health.overall_healthy as u8));
+        out.push_str("# HELP db_pool_system_memory_healthy 内存健康状态（1=健康，0=不健康）\n");
+        out.push_str("# TYPE db_pool_system_memory_healthy gauge\n");
+        out.push_str(&format!("db_pool_system_memory_healthy {}\n", health.memory_healthy as u8));
+        out.push_str("# HELP db_pool_system_cpu_healthy CPU健康状态（1=健康，0=不健康）\n");
+        out.push_str("# TYPE db_pool_system_cpu_healthy gauge\n");
+        out.push_str(&format!("db_pool_system_cpu_healthy {}\n", health.cpu_healthy as u8));
+        out.push_str("# HELP db_pool_system_disk_healthy 磁盘健康状态（1=健康，0=不健康）\n");
+        out.push_str("# TYPE db_pool_system_disk_healthy gauge\n");
+        out.push_str(&format!("db_pool_system_disk_healthy {}\n", health.disk_healthy as u8));
+        out.push_str("# HELP db_pool_system_network_healthy 网络健康状态（1=健康，0=不健康）\n");
+        out.push_str("# TYPE db_pool_system_network_healthy gauge\n");
+        out.push_str(&format!("db_pool_system_network_healthy {}\n", health.network_healthy as u8));
+
+        let active_alerts = self.alert_manager.get_active_alerts().await;
+        let mut alerts_by_severity: HashMap<&'static str, u64> = HashMap::new();
+        for severity in ["info", "warning", "critical"] {
+            alerts_by_severity.insert(severity, 0);
+        }
+        for alert in &active_alerts {
+            *alerts_by_severity.entry(severity_label(&alert.severity)).or_insert(0) += 1;
+        }
+
+        out.push_str("# HELP db_pool_active_alerts 当前活跃告警数\n");
+        out.push_str("# TYPE db_pool_active_alerts gauge\n");
+        for (severity, count) in &alerts_by_severity {
+            out.push_str(&format!("db_pool_active_alerts{{severity=\"{}\"}} {}\n", severity, count));
+        }
+
+        out
+    }
+}
+
+/// `AlertSeverity`对应的Prometheus标签值
+fn severity_label(severity: &AlertSeverity) -> &'static str {
+    match severity {
+        AlertSeverity::Info => "info",
+        AlertSeverity::Warning => "warning",
+        AlertSeverity::Critical => "critical",
+    }
+}
+
+/// Prometheus标签值转义：反斜杠、双引号与换行需要按文本暴露格式要求转义
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// `MonitoringTools::spawn_worker`的调度配置
+#[derive(Debug, Clone, Copy)]
+pub struct MonitorConfig {
+    /// 两次巡检之间的间隔；巡检本身（系统指标刷新+逐池健康检查+告警评估）的
+    /// 耗时随已注册连接池数量线性增长，间隔太短会让巡检相互堆叠
+    pub interval: Duration,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_millis(500),
+        }
+    }
+}
+
+/// `MonitoringTools::spawn_worker`返回的句柄：持有关闭信号的发送端与任务的
+/// `JoinHandle`，drop时自动发出关闭信号（不等待任务退出，需要等待请用
+/// `stop().await`）
+pub struct WorkerHandle {
+    shutdown_tx: Option<oneshot::Sender<()>>,
+    join_handle: Option<tokio::task::JoinHandle<()>>,
+    last_tick: Arc<RwLock<Option<Instant>>>,
+}
+
+impl WorkerHandle {
+    /// 最近一次完成巡检的时间；worker尚未跑完第一轮时为`None`
+    pub async fn last_tick(&self) -> Option<Instant> {
+        *self.last_tick.read().await
+    }
+
+    /// 发出关闭信号并等待worker任务退出
+    pub async fn stop(mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+        if let Some(handle) = self.join_handle.take() {
+            let _ = handle.await;
+        }
+    }
+}
+
+impl Drop for WorkerHandle {
+    fn drop(&mut self) {
+        if let Some(tx) = self.shutdown_tx.take() {
+            let _ = tx.send(());
+        }
+    }
 }
 
 /// 指标收集器
@@ -47,7 +290,11 @@ pub struct PoolMetricsData {
     pub failed_queries: u64,
     pub total_connections: u32,
     pub active_connections: u32,
-    pub query_latencies: Vec<Duration>,
+    /// 查询延迟（毫秒）的流式分位数草图，取代原先“保留最近1000条、每次查询
+    /// 都克隆+排序”的`Vec<Duration>`
+    pub latency_sketch: CkmsQuantile,
+    /// 所有已记录延迟之和，配合`total_queries`算均值，避免重新遍历整个样本集
+    pub total_latency: Duration,
     pub created_at: Instant,
     pub last_updated: Instant,
 }
@@ -86,7 +333,8 @@ impl MetricsCollector {
                 failed_queries: 0,
                 total_connections: 0,
                 active_connections: 0,
-                query_latencies: Vec::new(),
+                latency_sketch: CkmsQuantile::new(QUANTILE_EPSILON),
+                total_latency: Duration::ZERO,
                 created_at: Instant::now(),
                 last_updated: Instant::now(),
             }
@@ -94,18 +342,15 @@ impl MetricsCollector {
 
         pool_metrics.total_queries += 1;
         pool_metrics.last_updated = Instant::now();
-        
+
         if success {
             pool_metrics.successful_queries += 1;
         } else {
             pool_metrics.failed_queries += 1;
         }
 
-        // 保留最近1000个延迟记录
-        pool_metrics.query_latencies.push(latency);
-        if pool_metrics.query_latencies.len() > 1000 {
-            pool_metrics.query_latencies.remove(0);
-        }
+        pool_metrics.total_latency += latency;
+        pool_metrics.latency_sketch.insert(latency.as_secs_f64() * 1000.0);
     }
 
     /// 更新连接池连接数
@@ -136,26 +381,20 @@ impl MetricsCollector {
             0.0
         };
 
-        let (avg_latency, p99_latency) = if !pool_data.query_latencies.is_empty() {
-            let total_latency: Duration = pool_data.query_latencies.iter().sum();
-            let avg = total_latency / pool_data.query_latencies.len() as u32;
-            
-            let mut sorted_latencies = pool_data.query_latencies.clone();
-            sorted_latencies.sort();
-            let p99_index = (sorted_latencies.len() as f64 * 0.99) as usize;
-            let p99 = sorted_latencies.get(p99_index).cloned().unwrap_or(Duration::ZERO);
-            
-            (avg, p99)
+        let avg_latency_ms = if pool_data.total_queries > 0 {
+            (pool_data.total_latency.as_secs_f64() * 1000.0) / pool_data.total_queries as f64
         } else {
-            (Duration::ZERO, Duration::ZERO)
+            0.0
         };
 
         Some(PoolSummary {
             pool_id: pool_id.to_string(),
             queries_per_second: qps,
             error_rate,
-            avg_latency_ms: avg_latency.as_millis() as f64,
-            p99_latency_ms: p99_latency.as_millis() as f64,
+            avg_latency_ms,
+            p50_latency_ms: pool_data.latency_sketch.query(0.50),
+            p95_latency_ms: pool_data.latency_sketch.query(0.95),
+            p99_latency_ms: pool_data.latency_sketch.query(0.99),
             total_connections: pool_data.total_connections,
             active_connections: pool_data.active_connections,
             connection_utilization: if pool_data.total_connections > 0 {
@@ -181,6 +420,11 @@ impl MetricsCollector {
         summaries
     }
 
+    /// 已在本收集器留有记录的连接池ID列表
+    pub async fn known_pool_ids(&self) -> Vec<String> {
+        self.pool_metrics.read().await.keys().cloned().collect()
+    }
+
     /// 更新系统指标
     pub async fn update_system_metrics(&self) {
         let mut system_metrics = self.system_metrics.write().await;
@@ -224,6 +468,8 @@ pub struct PoolSummary {
     pub queries_per_second: f64,
     pub error_rate: f64,
     pub avg_latency_ms: f64,
+    pub p50_latency_ms: f64,
+    pub p95_latency_ms: f64,
     pub p99_latency_ms: f64,
     pub total_connections: u32,
     pub active_connections: u32,
@@ -244,10 +490,60 @@ impl Default for SystemMetrics {
     }
 }
 
+/// 熔断器连续失败阈值：达到该次数后从 Closed 切换到 Open
+const HEALTH_CIRCUIT_FAILURE_THRESHOLD: u32 = 3;
+/// Open状态的冷却时长：超过该时长后才允许下一次试探（Half-Open）
+const HEALTH_CIRCUIT_OPEN_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// `HealthChecker`为每个已注册连接池维护的熔断器状态，语义与
+/// `core::pool_manager::HealthMonitor`的熔断器一致（Closed → Open → HalfOpen），
+/// 但各自独立维护，互不共享状态
+#[derive(Debug, Clone)]
+struct HealthCircuitBreaker {
+    state: CircuitState,
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+}
+
+impl HealthCircuitBreaker {
+    fn new() -> Self {
+        Self {
+            state: CircuitState::Closed,
+            consecutive_failures: 0,
+            opened_at: None,
+        }
+    }
+
+    /// 冷却是否已结束（只有Open状态下才有意义）
+    fn cooldown_elapsed(&self) -> bool {
+        self.opened_at
+            .map(|t| t.elapsed() >= HEALTH_CIRCUIT_OPEN_COOLDOWN)
+            .unwrap_or(true)
+    }
+
+    fn record_success(&mut self) {
+        self.state = CircuitState::Closed;
+        self.consecutive_failures = 0;
+        self.opened_at = None;
+    }
+
+    fn record_failure(&mut self) {
+        self.consecutive_failures += 1;
+        if self.consecutive_failures >= HEALTH_CIRCUIT_FAILURE_THRESHOLD {
+            self.state = CircuitState::Open;
+            self.opened_at = Some(Instant::now());
+        }
+    }
+}
+
 /// 健康检查器
 pub struct HealthChecker {
     pool_health: RwLock<HashMap<String, PoolHealth>>,
     system_health: RwLock<SystemHealth>,
+    /// 已注册、可供`check_pool_health`真实探测的连接池；未注册的`pool_id`
+    /// 没有可探测的后端，`check_pool_health`对其保留“视为健康”的历史默认值
+    pools: RwLock<HashMap<String, Arc<dyn DatabasePool>>>,
+    breakers: RwLock<HashMap<String, HealthCircuitBreaker>>,
 }
 
 #[derive(Debug, Clone)]
@@ -257,6 +553,7 @@ pub struct PoolHealth {
     pub last_check: Instant,
     pub consecutive_failures: u32,
     pub last_error: Option<String>,
+    pub circuit_state: CircuitState,
 }
 
 #[derive(Debug, Clone)]
@@ -274,16 +571,81 @@ impl HealthChecker {
         Self {
             pool_health: RwLock::new(HashMap::new()),
             system_health: RwLock::new(SystemHealth::default()),
+            pools: RwLock::new(HashMap::new()),
+            breakers: RwLock::new(HashMap::new()),
         }
     }
 
-    /// 检查连接池健康状态
+    /// 注册一个连接池，供此后的`check_pool_health`通过其`health_check()`
+    /// （各后端内部经由一条`DatabaseConnection`做真实探测，例如SQLite走
+    /// `is_valid()`、Postgres/MSSQL走`SELECT 1`）做真实探测，而非直接返回健康
+    pub async fn register_pool(&self, pool_id: &str, pool: Arc<dyn DatabasePool>) {
+        self.pools.write().await.insert(pool_id.to_string(), pool);
+        self.breakers
+            .write()
+            .await
+            .entry(pool_id.to_string())
+            .or_insert_with(HealthCircuitBreaker::new);
+    }
+
+    /// 注销一个连接池：之后对该`pool_id`的`check_pool_health`不再做真实探测
+    pub async fn unregister_pool(&self, pool_id: &str) {
+        self.pools.write().await.remove(pool_id);
+        self.breakers.write().await.remove(pool_id);
+    }
+
+    /// 检查连接池健康状态：若`pool_id`已通过`register_pool`注册，则经其
+    /// `DatabaseConnection`做一次真实探测，并驱动Closed→Open→HalfOpen的熔断器；
+    /// Open状态下冷却未结束时直接判定为不健康，不再打挂已知故障的后端。
+    /// 未注册的`pool_id`没有可探测的后端，保留“视为健康”的历史默认值。
     pub async fn check_pool_health(&self, pool_id: &str) -> Result<bool, String> {
-        // 这里应该实现实际的健康检查逻辑
-        // 例如执行 SELECT 1 查询
-        
-        let is_healthy = true; // 模拟健康状态
-        let error = None;
+        let pool = self.pools.read().await.get(pool_id).cloned();
+
+        let (is_healthy, error) = if let Some(pool) = pool {
+            let skip_probe = {
+                let breakers = self.breakers.read().await;
+                breakers
+                    .get(pool_id)
+                    .map(|b| b.state == CircuitState::Open && !b.cooldown_elapsed())
+                    .unwrap_or(false)
+            };
+
+            if skip_probe {
+                (false, Some("circuit breaker open, skipping probe".to_string()))
+            } else {
+                // 冷却结束后的这一次探测即为Half-Open的单次试探
+                {
+                    let mut breakers = self.breakers.write().await;
+                    if let Some(breaker) = breakers.get_mut(pool_id) {
+                        if breaker.state == CircuitState::Open {
+                            breaker.state = CircuitState::HalfOpen;
+                        }
+                    }
+                }
+
+                match pool.health_check().await {
+                    Ok(healthy) => (healthy, None),
+                    Err(e) => (false, Some(e.to_string())),
+                }
+            }
+        } else {
+            (true, None)
+        };
+
+        let circuit_state = {
+            let mut breakers = self.breakers.write().await;
+            let breaker = breakers
+                .entry(pool_id.to_string())
+                .or_insert_with(HealthCircuitBreaker::new);
+
+            if is_healthy {
+                breaker.record_success();
+            } else {
+                breaker.record_failure();
+            }
+
+            breaker.state
+        };
 
         let mut health_map = self.pool_health.write().await;
         let health = health_map.entry(pool_id.to_string()).or_insert_with(|| {
@@ -293,12 +655,14 @@ impl HealthChecker {
                 last_check: Instant::now(),
                 consecutive_failures: 0,
                 last_error: None,
+                circuit_state: CircuitState::Closed,
             }
         });
 
         health.is_healthy = is_healthy;
         health.last_check = Instant::now();
         health.last_error = error;
+        health.circuit_state = circuit_state;
 
         if is_healthy {
             health.consecutive_failures = 0;
@@ -342,6 +706,20 @@ impl HealthChecker {
     }
 }
 
+impl SystemHealth {
+    /// 序列化为JSON：`Instant`本身不可序列化，`last_check`改为距今的秒数
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "overall_healthy": self.overall_healthy,
+            "memory_healthy": self.memory_healthy,
+            "cpu_healthy": self.cpu_healthy,
+            "disk_healthy": self.disk_healthy,
+            "network_healthy": self.network_healthy,
+            "last_check_secs_ago": self.last_check.elapsed().as_secs(),
+        })
+    }
+}
+
 impl Default for SystemHealth {
     fn default() -> Self {
         Self {
@@ -359,6 +737,44 @@ impl Default for SystemHealth {
 pub struct AlertManager {
     alert_rules: RwLock<Vec<AlertRule>>,
     active_alerts: RwLock<Vec<Alert>>,
+    /// 告警触发/解决事件广播，供 `/alerts` SSE端点订阅；没有订阅者时`send`会返回
+    /// Err，属于正常情况，直接忽略
+    events: broadcast::Sender<AlertView>,
+    /// 已注册的告警通知出口（webhook/Slack/PagerDuty等），参见
+    /// `crate::utils::alert_sinks::AlertSink`
+    sinks: RwLock<Vec<Arc<dyn crate::utils::alert_sinks::AlertSink>>>,
+}
+
+/// 一次告警触发或解决事件的JSON友好表示，供SSE推送；`Alert`本身携带`Instant`
+/// 不可直接序列化
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertView {
+    pub id: String,
+    pub rule_id: String,
+    pub pool_id: Option<String>,
+    pub message: String,
+    pub severity: AlertSeverity,
+    pub kind: AlertEventKind,
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEventKind {
+    Triggered,
+    Resolved,
+}
+
+impl Alert {
+    fn to_view(&self, kind: AlertEventKind) -> AlertView {
+        AlertView {
+            id: self.id.clone(),
+            rule_id: self.rule_id.clone(),
+            pool_id: self.pool_id.clone(),
+            message: self.message.clone(),
+            severity: self.severity.clone(),
+            kind,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -397,14 +813,44 @@ pub struct Alert {
     pub resolved_at: Option<Instant>,
 }
 
+/// 事件广播通道的缓冲容量：订阅者掉线超过这个积压量会收到一次`Lagged`
+const ALERT_EVENTS_CAPACITY: usize = 256;
+
 impl AlertManager {
     pub fn new() -> Self {
-        let alert_manager = Self {
+        let (events, _) = broadcast::channel(ALERT_EVENTS_CAPACITY);
+
+        Self {
             alert_rules: RwLock::new(Vec::new()),
             active_alerts: RwLock::new(Vec::new()),
-        };
+            events,
+            sinks: RwLock::new(Vec::new()),
+        }
+    }
+
+    /// 订阅告警触发/解决事件，供 `/alerts` SSE端点使用
+    pub fn subscribe(&self) -> broadcast::Receiver<AlertView> {
+        self.events.subscribe()
+    }
 
-        alert_manager
+    /// 注册一个告警通知出口：此后每次触发/解决事件都会异步推送给它，一个sink
+    /// 推送失败不影响其它sink或告警本身的判定
+    pub async fn register_sink(&self, sink: Arc<dyn crate::utils::alert_sinks::AlertSink>) {
+        self.sinks.write().await.push(sink);
+    }
+
+    /// 把一次触发/解决事件广播给SSE订阅者，并异步分发给所有已注册的sink
+    async fn dispatch_event(&self, view: AlertView) {
+        let _ = self.events.send(view.clone());
+
+        let sinks = self.sinks.read().await;
+        for sink in sinks.iter() {
+            let sink = Arc::clone(sink);
+            let view = view.clone();
+            tokio::spawn(async move {
+                sink.notify(&view).await;
+            });
+        }
     }
 
     pub async fn initialize_with_defaults(&self) {
@@ -504,20 +950,31 @@ impl AlertManager {
             resolved_at: None,
         };
 
+        let view = alert.to_view(AlertEventKind::Triggered);
+
         let mut alerts = self.active_alerts.write().await;
         alerts.push(alert);
+        drop(alerts);
+
+        self.dispatch_event(view).await;
 
         tracing::warn!("告警触发: {}", rule.name);
     }
 
     async fn resolve_alert(&self, rule_id: &str, pool_id: Option<&str>) {
         let alert_id = format!("{}_{}", rule_id, pool_id.unwrap_or("system"));
-        
-        let mut alerts = self.active_alerts.write().await;
-        if let Some(alert) = alerts.iter_mut().find(|a| a.id == alert_id && a.resolved_at.is_none()) {
+
+        let view = {
+            let mut alerts = self.active_alerts.write().await;
+            let Some(alert) = alerts.iter_mut().find(|a| a.id == alert_id && a.resolved_at.is_none()) else {
+                return;
+            };
             alert.resolved_at = Some(Instant::now());
             tracing::info!("告警已解决: {}", alert.message);
-        }
+            alert.to_view(AlertEventKind::Resolved)
+        };
+
+        self.dispatch_event(view).await;
     }
 
     /// 获取活跃告警
@@ -0,0 +1,88 @@
+use crate::core::error::{DbPoolError, Result};
+use crate::core::http;
+use crate::utils::monitoring::MonitoringTools;
+use std::sync::Arc;
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+
+/// `MonitoringTools`（`MetricsCollector`/`HealthChecker`/`AlertManager`）的轻量HTTP端点：
+/// `/metrics`返回Prometheus文本暴露格式，`/health`返回`SystemHealth`的JSON，`/alerts`
+/// 以Server-Sent Events持续推送`AlertManager::evaluate_alerts`触发/解决的告警
+///
+/// 不引入完整的HTTP框架，仅解析请求行；监听句柄随返回值丢弃而关闭，调用方通常用
+/// `tokio::spawn` 让其与连接池同生命周期
+pub struct MonitoringServer {
+    listener: TcpListener,
+    tools: Arc<MonitoringTools>,
+}
+
+impl MonitoringServer {
+    /// 绑定到 `addr`（如 `"0.0.0.0:9091"`），失败时返回 `DbPoolError::Monitoring`
+    pub async fn bind(addr: &str, tools: Arc<MonitoringTools>) -> Result<Self> {
+        let listener = http::bind(addr).await?;
+        Ok(Self { listener, tools })
+    }
+
+    /// 实际绑定到的本地地址（当 `addr` 使用端口 `0` 时用于获取分配到的端口）
+    pub fn local_addr(&self) -> Result<std::net::SocketAddr> {
+        http::local_addr(&self.listener)
+    }
+
+    /// 持续accept循环，每个连接独立处理；单个连接失败只记录日志，不影响端点继续服务
+    pub async fn serve(self) -> Result<()> {
+        loop {
+            let (stream, _) = self
+                .listener
+                .accept()
+                .await
+                .map_err(|e| DbPoolError::Monitoring(format!("accept失败: {}", e)))?;
+
+            let tools = self.tools.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, tools).await {
+                    tracing::warn!("monitoring端点处理连接失败: {}", e);
+                }
+            });
+        }
+    }
+}
+
+/// 读取请求行、丢弃请求头，按路径分发到对应的处理函数
+async fn handle_connection(mut stream: TcpStream, tools: Arc<MonitoringTools>) -> std::io::Result<()> {
+    let request_line = http::read_request_line(&mut stream).await?;
+
+    if request_line.starts_with("GET /metrics") {
+        let body = tools.render_prometheus().await;
+        http::write_response(&mut stream, "text/plain; version=0.0.4", &body).await
+    } else if request_line.starts_with("GET /health") {
+        let body = tools.health().check_system_health().await.to_json().to_string();
+        http::write_response(&mut stream, "application/json", &body).await
+    } else if request_line.starts_with("GET /alerts") {
+        stream_alerts(&mut stream, tools).await
+    } else {
+        http::write_response_with_status(&mut stream, 404, "Not Found", "text/plain", "not found").await
+    }
+}
+
+/// 保持连接打开，以`text/event-stream`持续推送`AlertManager`广播的触发/解决事件，
+/// 直到客户端断开连接或事件通道积压到`Lagged`
+async fn stream_alerts(stream: &mut TcpStream, tools: Arc<MonitoringTools>) -> std::io::Result<()> {
+    let header =
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\nConnection: keep-alive\r\n\r\n";
+    stream.write_all(header.as_bytes()).await?;
+
+    let mut receiver = tools.alerts().subscribe();
+    loop {
+        match receiver.recv().await {
+            Ok(event) => {
+                let payload = serde_json::to_string(&event).unwrap_or_default();
+                let frame = format!("data: {}\n\n", payload);
+                if stream.write_all(frame.as_bytes()).await.is_err() {
+                    return Ok(());
+                }
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => return Ok(()),
+        }
+    }
+}
@@ -0,0 +1,150 @@
+/// CKMS（Cormode-Korn-Muthukrishnan-Srivastava）流式分位数草图：用有界内存和
+/// 有界相对误差回答任意分位数查询，替代“保留全部样本、每次查询都重新排序”
+/// 的做法
+///
+/// 每个被保留的样本对应一个三元组`(value, g, delta)`：`g`是该样本与前一个被
+/// 保留样本之间被压缩掉的样本数（rank间隔），`delta`是该样本排名的容许误差
+/// 宽度。查询时靠`g`的累加近似重建排名，不需要访问被压缩掉的原始样本。
+#[derive(Debug, Clone)]
+pub struct CkmsQuantile {
+    epsilon: f64,
+    samples: Vec<(f64, u64, u64)>,
+    n: u64,
+    inserts_since_compress: u64,
+}
+
+/// 每插入这么多个样本就触发一次`compress`，避免`samples`随样本数无限增长
+const COMPRESS_INTERVAL: u64 = 100;
+
+impl CkmsQuantile {
+    pub fn new(epsilon: f64) -> Self {
+        Self {
+            epsilon,
+            samples: Vec::new(),
+            n: 0,
+            inserts_since_compress: 0,
+        }
+    }
+
+    /// 插入一个新样本：二分查找插入位置，按插入点左侧已保留样本的累计`g`
+    /// 估算该位置的近似排名，据此算出允许的`delta`（首尾位置恒为0）
+    pub fn insert(&mut self, value: f64) {
+        let i = self.samples.partition_point(|(v, _, _)| *v < value);
+
+        let delta = if i == 0 || i == self.samples.len() {
+            0
+        } else {
+            let rank: u64 = self.samples[..i].iter().map(|(_, g, _)| g).sum();
+            (2.0 * self.epsilon * rank as f64).floor() as u64
+        };
+
+        self.samples.insert(i, (value, 1, delta));
+        self.n += 1;
+        self.inserts_since_compress += 1;
+
+        if self.inserts_since_compress >= COMPRESS_INTERVAL {
+            self.compress();
+            self.inserts_since_compress = 0;
+        }
+    }
+
+    /// 从右到左扫描，只要相邻两个样本合并后仍落在`2*epsilon*n`的误差带内，
+    /// 就把左边样本的`g`累加到右边样本上、丢弃左边样本；首尾样本不参与合并，
+    /// 以保证最小/最大值始终精确可查。
+    fn compress(&mut self) {
+        if self.samples.len() < 3 {
+            return;
+        }
+
+        let threshold = 2.0 * self.epsilon * self.n as f64;
+        let mut i = self.samples.len() - 2;
+
+        loop {
+            let g_i = self.samples[i].1;
+            let g_next = self.samples[i + 1].1;
+            let delta_next = self.samples[i + 1].2;
+
+            if (g_i + g_next + delta_next) as f64 <= threshold {
+                self.samples[i + 1].1 += g_i;
+                self.samples.remove(i);
+            }
+
+            if i == 1 {
+                break;
+            }
+            i -= 1;
+        }
+    }
+
+    /// 查询分位数`phi`（取值`0.0..=1.0`）对应的近似值；草图为空时返回`0.0`
+    pub fn query(&self, phi: f64) -> f64 {
+        if self.samples.is_empty() {
+            return 0.0;
+        }
+
+        let target_rank = phi * self.n as f64;
+        let band = self.epsilon * self.n as f64;
+
+        let mut accumulated_g = 0u64;
+        for (value, g, delta) in &self.samples {
+            if (accumulated_g + g + delta) as f64 > target_rank + band {
+                return *value;
+            }
+            accumulated_g += g;
+        }
+
+        self.samples.last().map(|(v, _, _)| *v).unwrap_or(0.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CkmsQuantile;
+
+    #[test]
+    fn empty_sketch_queries_as_zero() {
+        let sketch = CkmsQuantile::new(0.01);
+        assert_eq!(sketch.query(0.5), 0.0);
+    }
+
+    #[test]
+    fn min_and_max_are_exact() {
+        let mut sketch = CkmsQuantile::new(0.01);
+        for v in 1..=1000 {
+            sketch.insert(v as f64);
+        }
+        assert_eq!(sketch.query(0.0), 1.0);
+        assert_eq!(sketch.query(1.0), 1000.0);
+    }
+
+    #[test]
+    fn approximates_quantiles_within_epsilon_band() {
+        let epsilon = 0.01;
+        let mut sketch = CkmsQuantile::new(epsilon);
+        for v in 1..=1000 {
+            sketch.insert(v as f64);
+        }
+
+        let n = 1000.0;
+        for phi in [0.5, 0.95, 0.99] {
+            let observed = sketch.query(phi);
+            let expected_rank = phi * n;
+            let observed_rank = observed;
+            assert!(
+                (observed_rank - expected_rank).abs() <= epsilon * n + 1.0,
+                "phi={phi} expected rank近似{expected_rank}, got {observed_rank}"
+            );
+        }
+    }
+
+    #[test]
+    fn compress_runs_without_losing_extremes_across_many_inserts() {
+        // 插入次数超过COMPRESS_INTERVAL多倍，强制多次触发compress()
+        let mut sketch = CkmsQuantile::new(0.05);
+        for v in 0..5_000 {
+            sketch.insert((v % 997) as f64);
+        }
+        assert_eq!(sketch.query(0.0), 0.0);
+        assert_eq!(sketch.query(1.0), 996.0);
+    }
+}
@@ -72,6 +72,8 @@ async fn test_alert_manager() {
         queries_per_second: 100.0,
         error_rate: 0.1, // 10% 错误率，应该触发告警
         avg_latency_ms: 50.0,
+        p50_latency_ms: 45.0,
+        p95_latency_ms: 120.0,
         p99_latency_ms: 150.0,
         total_connections: 10,
         active_connections: 8,
@@ -111,6 +113,8 @@ async fn test_alert_resolution() {
         queries_per_second: 100.0,
         error_rate: 0.1, // 高错误率
         avg_latency_ms: 50.0,
+        p50_latency_ms: 45.0,
+        p95_latency_ms: 120.0,
         p99_latency_ms: 150.0,
         total_connections: 10,
         active_connections: 8,
@@ -128,6 +132,8 @@ async fn test_alert_resolution() {
         queries_per_second: 100.0,
         error_rate: 0.01, // 正常错误率
         avg_latency_ms: 50.0,
+        p50_latency_ms: 45.0,
+        p95_latency_ms: 120.0,
         p99_latency_ms: 150.0,
         total_connections: 10,
         active_connections: 8,